@@ -1,6 +1,7 @@
-#[allow(nonstandard_style)]
+#![allow(nonstandard_style)]
 use bicliques::*;
 use iai::black_box;
+use rand::{rngs::StdRng, SeedableRng};
 use std::ops::ControlFlow;
 
 const T: bool = true;
@@ -13,23 +14,25 @@ macro_rules! bench {
 
             let right = DATA[0].len();
             let mut g = Bigraph::new(DATA.len() as u32, right as u32);
-            for (y, row) in DATA.iter().enumerate() {
+            for (x, row) in DATA.iter().enumerate() {
                 assert_eq!(row.len(), right);
-                for (x, _) in row.iter().enumerate().filter(|&(_, &t)| t) {
+                for (y, _) in row.iter().enumerate().filter(|&(_, &t)| t) {
                     g.add(Edge(x as u32, y as u32));
                 }
             }
 
-            biclique_covers::<(), _>(&g, $k, |c| {
-                black_box(c);
-                ControlFlow::Continue(())
-            });
+            covers(&g, $k);
         })+
-
-        iai::main!($($name),+);
     };
 }
 
+fn covers(g: &Bigraph, k: usize) {
+    let _ = biclique_covers::<(), _>(g, k, |c| {
+        black_box(c);
+        ControlFlow::Continue(())
+    });
+}
+
 bench!(
     mini, 3, &[&[T, T], &[f, T], &[T, f]];
     difficult5, 4, &[
@@ -55,3 +58,49 @@ bench!(
         &[f, T, T, T, T, T],
     ];
 );
+
+/// Enumerates the maximal covers of the complement of a permutation matrix,
+/// like `EX8` and `EX9` of the `count` binary.
+fn permutation_complement(n: u32, k: usize, symmetry: Symmetry) {
+    let g = generate::crown(n);
+    for c in BicliqueCovers::new(&g, k)
+        .maximal_only()
+        .with_symmetry(symmetry)
+    {
+        black_box(c);
+    }
+}
+
+fn ex9() {
+    permutation_complement(6, 5, Symmetry::Ignore);
+}
+
+fn ex9_symmetric() {
+    permutation_complement(6, 5, Symmetry::Representatives);
+}
+
+fn ex8() {
+    permutation_complement(7, 5, Symmetry::Ignore);
+}
+
+fn ex8_symmetric() {
+    permutation_complement(7, 5, Symmetry::Representatives);
+}
+
+fn planted8() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let (g, _) = generate::planted_cover(8, 8, 4, 0.4, &mut rng);
+    covers(&g, 4);
+}
+
+iai::main!(
+    mini,
+    difficult5,
+    nfaLEsynMIN,
+    difficult6,
+    planted8,
+    ex9,
+    ex9_symmetric,
+    ex8,
+    ex8_symmetric
+);
@@ -2,29 +2,8 @@
 
 use bicliques::*;
 
-use std::collections::HashSet;
 use std::ops::ControlFlow;
 
-fn from_str(s: &str) -> Bigraph {
-    let mut iter = s.split_whitespace();
-    let left = iter.next().unwrap().parse::<u32>().unwrap();
-    let right = iter.next().unwrap().parse::<u32>().unwrap();
-    let mut g = Bigraph::new(left, right);
-    for x in 0..left {
-        for y in 0..right {
-            match iter.next().unwrap() {
-                "1" => g.add(Edge(x, y)),
-                "_" => (),
-                e => panic!("unexpected: {}", e),
-            }
-        }
-    }
-
-    assert!(iter.next().is_none());
-
-    g
-}
-
 const EX0: &str = "5 5
 1 1 1 1 _
 1 1 _ 1 1
@@ -142,23 +121,15 @@ _ 1 1 1 1 1 1 1 1 _ _ 1 1 _ _ _
 ";
 
 fn main() {
-    let g = from_str(EX11);
-
-    println!("{:?}", g);
-
-    let mut i = 0;
-    let mut cliques = HashSet::new();
-    enum Never {}
-    bicliques::biclique_covers::<Never, _>(&g, g.left().min(g.right()) as usize - 1, |mut c| {
-        if g.is_maximal_cover(&c) {
-            if cliques.is_empty() {
-                println!("min: {}", c.cliques().len());
-            }
-            // println!("{}", c.print(&g));
-            assert!(cliques.insert(c));
-            i += 1;
-        }
-        ControlFlow::Continue(())
-    });
-    println!("{}", i);
+    let g: Bigraph = EX11.parse().unwrap();
+
+    print!("{}", g);
+
+    let max_size = g.left().min(g.right()) as usize - 1;
+    let counts = count_covers(&g, max_size);
+    if let Some(min) = counts.iter().position(|&count| count > 0) {
+        println!("min: {}", min);
+    }
+    println!("{}", counts.iter().sum::<u128>());
+    println!("{:?}", counts);
 }
@@ -0,0 +1,397 @@
+//! Counting biclique covers consisting of maximal bicliques without enumerating them.
+//!
+//! We pick an uncovered edge with the fewest remaining candidates and branch on
+//! which of its maximal bicliques is the first one in the cover, excluding the
+//! earlier ones from that branch. This way every cover is reached exactly once.
+//! Once all edges are covered, any subset of the bicliques which haven't been
+//! excluded may be added, so those covers are counted with binomial coefficients.
+//!
+//! [`sample_minimum_covers`] picks covers by their position in this order,
+//! descending into the branch which contains a position after counting them.
+use crate::reduce::Kernel;
+use crate::sample::SampleError;
+use crate::*;
+use std::mem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Free,
+    Chosen,
+    Excluded,
+}
+
+struct Counter {
+    cliques: Vec<Biclique>,
+    /// For each required edge, the bicliques containing it.
+    containing: Vec<Vec<usize>>,
+    /// For each biclique, the required edges it contains.
+    edges: Vec<Vec<usize>>,
+    states: Vec<State>,
+    /// For each required edge, the number of chosen bicliques containing it.
+    covered: Vec<u32>,
+    chosen: usize,
+    free: usize,
+    max_size: usize,
+    /// `binomials[n][t]` is `n` choose `t`, for `t` up to `max_size`.
+    binomials: Vec<Vec<u128>>,
+    counts: Vec<u128>,
+    /// Scratch space for [`Counter::lower_bound`].
+    blocked: Vec<bool>,
+}
+
+impl Counter {
+    fn new(g: &Bigraph, max_size: usize) -> Counter {
+        let entries: Vec<Edge> = g.entries().collect();
+        let mut containing = vec![Vec::new(); entries.len()];
+        let mut cliques = Vec::new();
+        let mut edges = Vec::new();
+        for clique in maximal_bicliques(g) {
+            let contained: Vec<usize> = (0..entries.len())
+                .filter(|&j| {
+                    let Edge(x, y) = entries[j];
+                    clique.left.get(x) && clique.right.get(y)
+                })
+                .collect();
+            // Bicliques consisting of optional entries only are never needed.
+            if contained.is_empty() {
+                continue;
+            }
+
+            for &j in &contained {
+                containing[j].push(edges.len());
+            }
+            cliques.push(clique);
+            edges.push(contained);
+        }
+
+        let mut binomials: Vec<Vec<u128>> = Vec::with_capacity(edges.len() + 1);
+        for n in 0..=edges.len() {
+            let row = (0..=max_size)
+                .map(|t| match (n, t) {
+                    (_, 0) => 1,
+                    (0, _) => 0,
+                    _ => binomials[n - 1][t - 1].saturating_add(binomials[n - 1][t]),
+                })
+                .collect();
+            binomials.push(row);
+        }
+
+        Counter {
+            covered: vec![0; entries.len()],
+            states: vec![State::Free; edges.len()],
+            blocked: vec![false; edges.len()],
+            free: edges.len(),
+            cliques,
+            containing,
+            edges,
+            chosen: 0,
+            max_size,
+            binomials,
+            counts: vec![0; max_size + 1],
+        }
+    }
+
+    fn set_chosen(&mut self, c: usize, chosen: bool) {
+        self.states[c] = if chosen { State::Chosen } else { State::Free };
+        for &j in &self.edges[c] {
+            if chosen {
+                self.covered[j] += 1;
+            } else {
+                self.covered[j] -= 1;
+            }
+        }
+    }
+
+    /// The uncovered edge with the fewest candidates left,
+    /// or `None` if all edges are covered.
+    fn next_edge(&self) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for (j, containing) in self.containing.iter().enumerate() {
+            if self.covered[j] > 0 {
+                continue;
+            }
+
+            let candidates = containing
+                .iter()
+                .filter(|&&c| self.states[c] == State::Free)
+                .count();
+            if best.is_none_or(|(_, n)| candidates < n) {
+                best = Some((j, candidates));
+                if candidates == 0 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The number of bicliques needed to cover the remaining edges, at least.
+    ///
+    /// We greedily collect uncovered edges which aren't part of a common
+    /// biclique, each of them has to be covered by a different one.
+    fn lower_bound(&mut self) -> usize {
+        let mut bound = 0;
+        for (j, containing) in self.containing.iter().enumerate() {
+            let free = || {
+                containing
+                    .iter()
+                    .filter(|&&c| self.states[c] == State::Free)
+            };
+            if self.covered[j] > 0 || free().any(|&c| self.blocked[c]) {
+                continue;
+            }
+
+            bound += 1;
+            for &c in free() {
+                self.blocked[c] = true;
+            }
+        }
+
+        self.blocked.fill(false);
+        bound
+    }
+
+    /// The free bicliques containing the edge `j`.
+    fn candidates(&self, j: usize) -> Vec<usize> {
+        self.containing[j]
+            .iter()
+            .copied()
+            .filter(|&c| self.states[c] == State::Free)
+            .collect()
+    }
+
+    /// The free bicliques containing the edge `j` and all other uncovered edges.
+    fn fitting(&self, j: usize) -> Vec<usize> {
+        let uncovered: Vec<usize> = (0..self.covered.len())
+            .filter(|&j| self.covered[j] == 0)
+            .collect();
+        self.candidates(j)
+            .into_iter()
+            .filter(|&c| {
+                uncovered
+                    .iter()
+                    .all(|j| self.edges[c].binary_search(j).is_ok())
+            })
+            .collect()
+    }
+
+    fn choose(&mut self, c: usize) {
+        self.set_chosen(c, true);
+        self.chosen += 1;
+        self.free -= 1;
+    }
+
+    /// Undoes [`Counter::choose`], excluding `c` from the later branches.
+    fn exclude(&mut self, c: usize) {
+        self.chosen -= 1;
+        self.set_chosen(c, false);
+        self.states[c] = State::Excluded;
+    }
+
+    /// Makes the bicliques excluded by [`Counter::exclude`] free again.
+    fn release(&mut self, excluded: &[usize]) {
+        for &c in excluded {
+            self.states[c] = State::Free;
+        }
+        self.free += excluded.len();
+    }
+
+    fn count(&mut self) {
+        let Some((j, candidates)) = self.next_edge() else {
+            for t in 0..=self.free.min(self.max_size - self.chosen) {
+                let count = &mut self.counts[self.chosen + t];
+                *count = count.saturating_add(self.binomials[self.free][t]);
+            }
+            return;
+        };
+
+        let remaining = self.max_size - self.chosen;
+        if candidates == 0 || remaining == 0 {
+            return;
+        } else if remaining == 1 {
+            // The last biclique has to cover all remaining edges.
+            let fitting = self.fitting(j).len() as u128;
+            let count = &mut self.counts[self.max_size];
+            *count = count.saturating_add(fitting);
+            return;
+        } else if self.lower_bound() > remaining {
+            return;
+        }
+
+        let candidates = self.candidates(j);
+        for &c in &candidates {
+            self.choose(c);
+            self.count();
+            self.exclude(c);
+        }
+        self.release(&candidates);
+    }
+
+    /// The number of covers with `max_size` bicliques below the current branch.
+    fn count_largest(&mut self) -> u128 {
+        let counts = mem::replace(&mut self.counts, vec![0; self.max_size + 1]);
+        self.count();
+        mem::replace(&mut self.counts, counts)[self.max_size]
+    }
+
+    /// Finds the covers with `max_size` bicliques below the current branch at the
+    /// positions given by `picks`, in the order in which they're counted. For each
+    /// `(position, sample)`, sorted by their position, the indices of the bicliques
+    /// are stored in `selected[sample]`.
+    fn select(&mut self, picks: &[(u128, usize)], selected: &mut [Vec<usize>]) {
+        if picks.is_empty() {
+            return;
+        }
+
+        let chosen = |states: &[State]| -> Vec<usize> {
+            (0..states.len())
+                .filter(|&c| states[c] == State::Chosen)
+                .collect()
+        };
+
+        let Some((j, _)) = self.next_edge() else {
+            // Unranks the combinations of free bicliques in lexicographic order.
+            let free: Vec<usize> = (0..self.states.len())
+                .filter(|&c| self.states[c] == State::Free)
+                .collect();
+            for &(mut position, sample) in picks {
+                let mut cliques = chosen(&self.states);
+                let mut needed = self.max_size - self.chosen;
+                for (i, &c) in free.iter().enumerate() {
+                    if needed == 0 {
+                        break;
+                    }
+
+                    let with_c = self.binomials[free.len() - i - 1][needed - 1];
+                    if position < with_c {
+                        cliques.push(c);
+                        needed -= 1;
+                    } else {
+                        position -= with_c;
+                    }
+                }
+                selected[sample] = cliques;
+            }
+            return;
+        };
+
+        if self.max_size - self.chosen == 1 {
+            let fitting = self.fitting(j);
+            for &(position, sample) in picks {
+                let mut cliques = chosen(&self.states);
+                cliques.push(fitting[position as usize]);
+                selected[sample] = cliques;
+            }
+            return;
+        }
+
+        let mut picks = picks;
+        let mut offset = 0;
+        let mut excluded = Vec::new();
+        for c in self.candidates(j) {
+            if picks.is_empty() {
+                break;
+            }
+
+            self.choose(c);
+            let count = self.count_largest();
+            let (inside, rest) =
+                picks.split_at(picks.partition_point(|&(p, _)| p < offset + count));
+            let inside: Vec<(u128, usize)> = inside.iter().map(|&(p, s)| (p - offset, s)).collect();
+            self.select(&inside, selected);
+            self.exclude(c);
+            excluded.push(c);
+            picks = rest;
+            offset += count;
+        }
+        self.release(&excluded);
+    }
+}
+
+/// The minimum covers of a connected bigraph consisting of distinct maximal
+/// bicliques, see [`sample_minimum_covers`].
+pub(crate) struct MinimumCovers {
+    kernel: Kernel,
+    counter: Counter,
+    len: u128,
+}
+
+impl MinimumCovers {
+    pub(crate) fn new(g: &Bigraph) -> Result<MinimumCovers, SampleError> {
+        let kernel = Kernel::new(g, true);
+        let lower = forced::fooling_set(&kernel.graph).len();
+        let upper = heuristic_cover(&kernel.graph, HeuristicOptions::default())
+            .cliques()
+            .len();
+        let mut counter = Counter::new(&kernel.graph, upper);
+        // Only counting covers of a single size prunes the branches
+        // which need more bicliques, so we never count larger covers.
+        for size in lower..=upper {
+            counter.max_size = size;
+            let len = counter.count_largest();
+            if len == u128::MAX {
+                return Err(SampleError::TooManyCovers);
+            } else if len > 0 {
+                return Ok(MinimumCovers {
+                    kernel,
+                    counter,
+                    len,
+                });
+            }
+        }
+
+        Err(SampleError::NoCover)
+    }
+
+    /// The number of minimum covers.
+    pub(crate) fn len(&self) -> u128 {
+        self.len
+    }
+
+    /// The bicliques of the covers at the positions given by `picks`, see [`Counter::select`].
+    pub(crate) fn select(&mut self, picks: &[(u128, usize)], samples: usize) -> Vec<Vec<Biclique>> {
+        let mut selected = vec![Vec::new(); samples];
+        self.counter.select(picks, &mut selected);
+        selected
+            .into_iter()
+            .map(|indices| {
+                indices
+                    .into_iter()
+                    .map(|c| self.kernel.lift(&self.counter.cliques[c]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Counts the biclique covers of `g` consisting of distinct maximal bicliques,
+/// by their size up to `max_size`.
+///
+/// The `s`-th element of the result is the number of such covers with `s`
+/// bicliques. These are the covers yielded by [`BicliqueCovers::maximal_only`],
+/// but the covers are never constructed, which is a lot faster if there are
+/// many of them. Optional entries are treated like in [`Bigraph::is_maximal`],
+/// bicliques which only consist of optional entries are left out. Counts which
+/// don't fit into a `u128` are saturated.
+pub fn count_covers(g: &Bigraph, max_size: usize) -> Vec<u128> {
+    let mut counts: Vec<u128> = vec![0; max_size + 1];
+    counts[0] = 1;
+    for (component, _) in g.components() {
+        // Maximal bicliques contain either all twins or none of them.
+        let kernel = Kernel::new(&component, true);
+        let mut counter = Counter::new(&kernel.graph, max_size);
+        counter.count();
+
+        // Covers of the whole bigraph combine a cover of each component.
+        let mut combined = vec![0u128; max_size + 1];
+        for (s, &a) in counts.iter().enumerate() {
+            for (t, &b) in counter.counts[..=max_size - s].iter().enumerate() {
+                combined[s + t] = combined[s + t].saturating_add(a.saturating_mul(b));
+            }
+        }
+        counts = combined;
+    }
+
+    counts
+}
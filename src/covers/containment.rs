@@ -1,17 +1,22 @@
+use super::symmetry::Symmetries;
 use crate::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Edge {
     data: Box<[Biclique]>,
     maximal: usize,
     empty: usize,
+    /// The position of the layer in the search tree, see [`Containment::start_root`].
+    path: Box<[u32]>,
 }
 
 impl Edge {
-    fn new(g: &Bigraph, mut data: Box<[Biclique]>) -> Edge {
+    fn new(g: &Bigraph, mut data: Box<[Biclique]>, path: Box<[u32]>) -> Edge {
         let mut maximal = 0;
         for i in 0..data.len() {
-            if g.is_maximal(&data[i]) {
+            // The empty biclique is only maximal in a bigraph without rows and
+            // columns, but `contains_slow` never uses it to cover anything.
+            if !data[i].is_empty() && g.is_maximal(&data[i]) {
                 data.swap(i, maximal);
                 maximal += 1;
             }
@@ -31,6 +36,7 @@ impl Edge {
             data,
             maximal,
             empty,
+            path,
         }
     }
 
@@ -51,14 +57,61 @@ impl Edge {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct Containment {
+    enabled: bool,
+    /// Stop adding new entries once there are this many.
+    max_entries: Option<usize>,
     entries: Vec<Edge>,
     layers: Vec<(usize, Box<[Biclique]>)>,
+    /// The position of the last layer in `layers`, given by the index of the branch
+    /// taken at each of its ancestors.
+    path: Vec<u32>,
+    /// The number of branches taken so far at each layer in `layers`.
+    branches: Vec<u32>,
+    /// The number of entries which were explored before the current subtree.
+    known: usize,
 }
 
-fn contains_reject(data: &[Biclique], edge: &Edge) -> bool {
+/// How the bicliques of a layer are compared to the ones of an explored layer.
+trait Compare {
+    fn eq(&self, c: &Biclique, clique: &Biclique) -> bool;
+    fn contains_clique(&self, c: &Biclique, clique: &Biclique) -> bool;
+}
+
+struct Identity;
+
+impl Compare for Identity {
+    fn eq(&self, c: &Biclique, clique: &Biclique) -> bool {
+        c == clique
+    }
+
+    fn contains_clique(&self, c: &Biclique, clique: &Biclique) -> bool {
+        c.contains_clique(clique)
+    }
+}
+
+/// Compares the images of the bicliques under an automorphism,
+/// given its inverse, without computing them.
+struct Image<'a>(&'a Automorphism);
+
+impl Compare for Image<'_> {
+    fn eq(&self, c: &Biclique, clique: &Biclique) -> bool {
+        c.left.element_count() == clique.left.element_count()
+            && c.right.element_count() == clique.right.element_count()
+            && self.contains_clique(c, clique)
+    }
+
+    fn contains_clique(&self, c: &Biclique, clique: &Biclique) -> bool {
+        let inverse = self.0;
+        clique.left.iter().all(|x| c.left.get(inverse.left(x)))
+            && clique.right.iter().all(|y| c.right.get(inverse.right(y)))
+    }
+}
+
+fn contains_reject(data: &[Biclique], edge: &Edge, compare: &impl Compare) -> bool {
     for c in edge.maximal() {
-        if !data.iter().any(|q| q == c) {
+        if !data.iter().any(|q| compare.eq(q, c)) {
             return false;
         }
     }
@@ -66,7 +119,7 @@ fn contains_reject(data: &[Biclique], edge: &Edge) -> bool {
     if edge
         .tail()
         .iter()
-        .any(|clique| data.iter().all(|c| !c.contains_clique(clique)))
+        .any(|clique| data.iter().all(|c| !compare.contains_clique(c, clique)))
     {
         return false;
     }
@@ -124,7 +177,7 @@ fn solve_superset(mut superset: Vec<TBitSet<usize>>) -> bool {
     recurse(superset)
 }
 
-fn contains_slow(data: &[Biclique], edge: &Edge) -> bool {
+fn contains_slow(data: &[Biclique], edge: &Edge, compare: &impl Compare) -> bool {
     let non_empty = edge.non_empty();
     let mut superset = vec![TBitSet::new(); non_empty.len()];
     for (i, c) in data.iter().enumerate() {
@@ -133,7 +186,7 @@ fn contains_slow(data: &[Biclique], edge: &Edge) -> bool {
         }
 
         for (j, clique) in non_empty.iter().enumerate() {
-            if c.contains_clique(clique) {
+            if compare.contains_clique(c, clique) {
                 superset[j].add(i);
             }
         }
@@ -177,49 +230,185 @@ fn contains_stupid(data: &[Biclique], edge: &Edge) -> bool {
 }
 */
 
+fn contains_by(data: &[Biclique], edge: &Edge, compare: &impl Compare) -> bool {
+    contains_reject(data, edge, compare) && contains_slow(data, edge, compare)
+}
+
 fn contains(data: &[Biclique], edge: &Edge) -> bool {
-    contains_reject(data, edge) && contains_slow(data, edge)
+    contains_by(data, edge, &Identity)
 }
 
 impl Containment {
-    pub(crate) fn init(init: &[Biclique]) -> Containment {
-        let mut c = Containment {
+    pub(crate) fn new(max_entries: Option<usize>) -> Containment {
+        Containment {
+            enabled: true,
+            max_entries,
             entries: Vec::new(),
             layers: vec![],
-        };
-        c.reinit(init);
-        c
+            path: Vec::new(),
+            branches: Vec::new(),
+            known: 0,
+        }
     }
 
-    pub(crate) fn reinit(&mut self, init: &[Biclique]) {
+    /// A containment which never discards any layers.
+    pub(crate) fn disabled() -> Containment {
+        Containment {
+            enabled: false,
+            ..Containment::new(None)
+        }
+    }
+
+    /// The number of explored layers we currently remember.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Forgets about all layers, including the ones which are still in progress.
+    pub(crate) fn clear(&mut self) {
         self.entries.clear();
+        self.layers.clear();
+        self.path.clear();
+        self.branches.clear();
+        self.known = 0;
+    }
+
+    pub(crate) fn reinit(&mut self, init: &[Biclique]) {
+        self.clear();
+        self.start_root(init, &[]);
+    }
+
+    /// Starts exploring the subtree at `path` of a search for covers of the same size,
+    /// keeping the layers explored before.
+    ///
+    /// Layers are discarded because the search already found their covers at an earlier
+    /// position, either below the explored layer or in a branch which came before it.
+    /// Only the explored layers which come before the whole subtree in a depth-first
+    /// search are used, so no cover ends up discarded in favor of one which was.
+    pub(crate) fn start_root(&mut self, init: &[Biclique], path: &[u32]) {
+        if !self.enabled {
+            return;
+        }
+
         assert!(self.layers.is_empty());
-        self.layers.push((0, init.to_owned().into_boxed_slice()));
+        self.entries
+            .retain(|e| *e.path < *path && !path.starts_with(&e.path));
+        self.known = self.entries.len();
+
+        self.layers
+            .push((self.entries.len(), init.to_owned().into_boxed_slice()));
+        self.path.clear();
+        self.path.extend_from_slice(path);
+        self.branches.push(0);
     }
 
+    /// Moves the layers explored in the current subtree to `other`.
+    pub(crate) fn share(&mut self, other: &mut Containment) {
+        other.entries.extend(self.entries.drain(self.known..));
+    }
+
+    /// Takes the next branch of the layer at `depth` to explore it
+    /// elsewhere, returning the position of the branch.
+    pub(crate) fn split_off(&mut self, depth: usize) -> Box<[u32]> {
+        if !self.enabled {
+            return Box::new([]);
+        }
+
+        let root = self.path.len() + 1 - self.layers.len();
+        let mut path = self.path[..root + depth].to_vec();
+        path.push(self.branches[depth]);
+        self.branches[depth] += 1;
+        path.into_boxed_slice()
+    }
+
+    /// Starts exploring the layer with the bicliques `data`, unless it
+    /// contains an explored layer.
     pub(crate) fn start_layer(&mut self, data: &[Biclique]) -> bool {
-        if self.should_discard(data) {
+        if !self.enabled {
+            return true;
+        }
+
+        if self.contains_explored(data) {
             return false;
         }
 
+        self.enter_layer(data);
+        true
+    }
+
+    /// Like [`Containment::start_layer`], for a layer which
+    /// has already been checked by [`Containment::should_discard`].
+    pub(crate) fn enter_layer(&mut self, data: &[Biclique]) {
+        if !self.enabled {
+            return;
+        }
+
         self.layers
             .push((self.entries.len(), data.to_owned().into_boxed_slice()));
-        true
+        let branch = self.branches.last_mut().unwrap();
+        self.path.push(*branch);
+        *branch += 1;
+        self.branches.push(0);
     }
 
     pub(crate) fn finish_layer(&mut self, g: &Bigraph, data: Box<[Biclique]>) {
+        if !self.enabled {
+            return;
+        }
+
         let (start, clique) = self.layers.pop().unwrap();
-        let clique = Edge::new(g, clique);
+        self.branches.pop();
+        let clique = Edge::new(g, clique, self.path.as_slice().into());
+        if !self.layers.is_empty() {
+            self.path.pop();
+        }
         debug_assert!(contains(&data, &clique));
 
         for child in self.entries.drain(start..) {
             debug_assert!(contains(&child.data, &clique));
         }
 
-        self.entries.push(clique);
+        // Not remembering a layer only means that we
+        // won't discard the layers contained in it.
+        if self.max_entries.is_none_or(|max| self.entries.len() < max) {
+            self.entries.push(clique);
+        }
+    }
+
+    /// Whether `data` or its image under one of the `symmetries` contains
+    /// an explored layer, see [`Symmetries`].
+    pub(crate) fn should_discard(&self, data: &[Biclique], symmetries: &mut Symmetries) -> bool {
+        if self.contains_explored(data) {
+            return true;
+        } else if symmetries.is_empty() {
+            return false;
+        }
+
+        let empty = data.iter().filter(|&c| c.is_empty()).count();
+        for e in &self.entries {
+            if empty > e.empty() {
+                continue;
+            }
+
+            // The image has to contain the maximal bicliques of `e` and supersets
+            // of the others, which only leaves a few automorphisms to check.
+            let candidates = match (e.maximal().first(), e.tail().first()) {
+                (Some(clique), _) => symmetries.mapping_onto(data, clique),
+                (None, Some(clique)) => symmetries.mapping_into(data, clique),
+                (None, None) => symmetries.all(),
+            };
+            if candidates
+                .iter()
+                .any(|&i| contains_by(data, e, &Image(symmetries.inverse(i))))
+            {
+                return true;
+            }
+        }
+
+        false
     }
 
-    pub(crate) fn should_discard(&mut self, data: &[Biclique]) -> bool {
+    fn contains_explored(&self, data: &[Biclique]) -> bool {
         let empty = data.iter().filter(|&c| c.is_empty()).count();
         for e in &self.entries {
             if empty > e.empty() {
@@ -0,0 +1,36 @@
+use std::time::Instant;
+
+/// Limits for the search of biclique covers, e.g. [`BicliqueCovers::with_limits`](super::BicliqueCovers::with_limits).
+///
+/// The default does not limit the search at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Stop once the deadline has passed.
+    pub deadline: Option<Instant>,
+    /// Stop after exploring the given number of layers, i.e. partial covers.
+    pub max_layers: Option<u64>,
+    /// Stop remembering fully explored layers once there are this many of them.
+    ///
+    /// Unlike the other limits this doesn't stop the search,
+    /// it only makes it worse at discarding redundant layers.
+    pub max_containment_entries: Option<usize>,
+}
+
+/// Whether a search ran until the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    Complete,
+    /// The search stopped early because it reached one of its [`SearchLimits`].
+    CutOff {
+        /// A lower bound for the boolean rank.
+        lower_bound: usize,
+        /// The size of the smallest cover found so far, if any.
+        upper_bound: Option<usize>,
+    },
+}
+
+impl Completion {
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Completion::Complete)
+    }
+}
@@ -2,11 +2,36 @@ use crate::*;
 use std::mem;
 
 mod containment;
+mod limits;
+mod monitor;
+mod parallel;
+mod product;
+mod symmetry;
 
+use crate::reduce::Kernel;
 use containment::Containment;
+pub use limits::{Completion, SearchLimits};
+use monitor::Monitor;
+pub use monitor::SearchStats;
+pub use parallel::ParallelOptions;
+pub(crate) use parallel::{par_covers, par_maximal_covers};
+use product::Product;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+pub use symmetry::Symmetry;
+use symmetry::{sorted, Symmetries};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Every edge is part of at least one biclique.
+    Cover,
+    /// Every edge is part of exactly one biclique.
+    Partition,
+}
 
 #[derive(Debug, Clone)]
 struct Layer {
+    mode: Mode,
     /// The part which is currently forced.
     bicliques: Box<[Biclique]>,
     data: TBitSet<usize>,
@@ -35,7 +60,7 @@ impl Layer {
         DataIndex(g.edge_index(e) * (k + POSSIBILITY_OFFSET))
     }
 
-    fn initial(g: &Bigraph, k: usize, forced: &[Edge]) -> Layer {
+    fn initial(g: &Bigraph, mode: Mode, k: usize, forced: &[Edge]) -> Layer {
         let mut bicliques: Vec<Biclique> = forced
             .iter()
             .map(|&Edge(x, y)| Biclique {
@@ -48,6 +73,7 @@ impl Layer {
         // WARNING: `data` is still inconsistent here,
         // we have to be careful about which methods we use.
         let mut layer = Layer {
+            mode,
             bicliques: bicliques.into_boxed_slice(),
             data: TBitSet::new(),
             changed: (0..k).collect(),
@@ -99,6 +125,10 @@ impl Layer {
                                     assert!(!self.bicliques[c].contains(edge));
                                 }
                             }
+
+                            if self.mode == Mode::Partition {
+                                assert!(self.bicliques.iter().filter(|c| c.contains(edge)).count() <= 1);
+                            }
                         } else {
                             assert!(!self.data.get(index.in_biclique()));
                             for c in self.cliques() {
@@ -115,18 +145,20 @@ impl Layer {
 
     fn add_left(&mut self, g: &Bigraph, c: usize, x: u32) {
         for y in self.bicliques[c].right.iter() {
-            let index = Layer::index(g, self.bicliques.len(), Edge(x, y));
-            self.data.add(index.in_biclique());
-            self.data.remove(index.may_add(c));
+            if g.get(Edge(x, y)) {
+                let index = Layer::index(g, self.bicliques.len(), Edge(x, y));
+                self.data.add(index.in_biclique());
+                self.data.remove(index.may_add(c));
+            }
         }
 
         'outer: for y in 0..g.right {
-            if g.get(Edge(x, y)) {
+            if g.allows(Edge(x, y)) {
                 continue 'outer;
             }
 
             for x in self.bicliques[c].left.iter() {
-                if !g.get(Edge(x, y)) {
+                if !g.allows(Edge(x, y)) {
                     continue 'outer;
                 }
             }
@@ -147,18 +179,20 @@ impl Layer {
 
     fn add_right(&mut self, g: &Bigraph, c: usize, y: u32) {
         for x in self.bicliques[c].left.iter() {
-            let index = Layer::index(g, self.bicliques.len(), Edge(x, y));
-            self.data.add(index.in_biclique());
-            self.data.remove(index.may_add(c));
+            if g.get(Edge(x, y)) {
+                let index = Layer::index(g, self.bicliques.len(), Edge(x, y));
+                self.data.add(index.in_biclique());
+                self.data.remove(index.may_add(c));
+            }
         }
 
         'outer: for x in 0..g.left {
-            if g.get(Edge(x, y)) {
+            if g.allows(Edge(x, y)) {
                 continue 'outer;
             }
 
             for y in self.bicliques[c].right.iter() {
-                if !g.get(Edge(x, y)) {
+                if !g.allows(Edge(x, y)) {
                     continue 'outer;
                 }
             }
@@ -189,7 +223,40 @@ impl Layer {
         self.consistent(g)
     }
 
+    /// Whether adding `e` to the biclique `c` would cover an edge
+    /// which is already part of a different biclique.
+    fn would_overlap(&self, g: &Bigraph, c: usize, Edge(x, y): Edge) -> bool {
+        let k = self.bicliques.len();
+        let clique = &self.bicliques[c];
+        let covered = |e| g.get(e) && self.data.get(Layer::index(g, k, e).in_biclique());
+        if !clique.left.get(x) && clique.right.iter().chain([y]).any(|y| covered(Edge(x, y))) {
+            return true;
+        }
+
+        !clique.right.get(y) && clique.left.iter().chain([x]).any(|x| covered(Edge(x, y)))
+    }
+
+    /// When computing partitions, we must not add edges to a biclique if that
+    /// would cover an already covered edge a second time.
+    fn restrict_partition(&mut self, g: &Bigraph) {
+        if self.mode != Mode::Partition {
+            return;
+        }
+
+        for e in g.entries() {
+            let index = Layer::index(g, self.bicliques.len(), e);
+            for c in self.cliques() {
+                if self.data.get(index.may_add(c)) && self.would_overlap(g, c, e) {
+                    self.data.remove(index.may_add(c));
+                }
+            }
+        }
+
+        self.consistent(g)
+    }
+
     fn forced_updates(&mut self, g: &Bigraph) -> Result<(), ()> {
+        self.restrict_partition(g);
         let mut changed = true;
         while changed {
             changed = false;
@@ -214,6 +281,7 @@ impl Layer {
                 if let Some(c) = edge {
                     changed = true;
                     self.add_edge(g, c, e);
+                    self.restrict_partition(g);
                 } else {
                     return Err(());
                 }
@@ -227,7 +295,17 @@ impl Layer {
     /// Guesses an edge, removing it from `self` and returning
     /// a new layer with the chosen edge.
     fn guess_edge(&mut self, g: &Bigraph) -> Option<Layer> {
-        for max_choices in 2..self.bicliques.len() {
+        let max_choices = match self.mode {
+            // Closing a biclique over optional entries may not be unique,
+            // so the edges which may be part of every biclique have to be
+            // guessed as well.
+            Mode::Cover if g.has_optional() => self.bicliques.len() + 1,
+            Mode::Cover => self.bicliques.len(),
+            // Without extending bicliques to be maximal, we also have
+            // to guess edges which may be part of every biclique.
+            Mode::Partition => self.bicliques.len() + 1,
+        };
+        for max_choices in 2..max_choices {
             for e in g.entries() {
                 let index = Layer::index(g, self.bicliques.len(), e);
                 let num_choices = self
@@ -261,6 +339,19 @@ impl Layer {
                             }
                         } else if prev_cliques.left.is_empty() && prev_cliques.right.is_empty() {
                             self.data.remove(index.may_add(c));
+                            if self.mode == Mode::Partition {
+                                // All empty bicliques are interchangeable, so if `e` is
+                                // not part of `c`, it can't be part of any empty biclique.
+                                for i in self.cliques() {
+                                    if self.bicliques[i].is_empty() {
+                                        self.data.remove(index.may_add(i));
+                                    }
+                                }
+                            }
+                        } else if g.has_optional() {
+                            // Bicliques are only closed over edges, so `e` may be
+                            // unrelated to `c` even though it may be added to it.
+                            self.data.remove(index.may_add(c));
                         } else {
                             continue 'cliques;
                         }
@@ -276,124 +367,676 @@ impl Layer {
     }
 }
 
-fn iterate_sat<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
-    g: &Bigraph,
-    containment: &mut Containment,
-    mut layer: Layer,
-    f: &mut F,
-) -> ControlFlow<T> {
-    while let Some(mut new_layer) = layer.guess_edge(g) {
-        match restrict_layer(g, &mut new_layer) {
-            Ok(()) => (),
-            Err(()) => continue,
-        }
+fn left_maximal(g: &Bigraph, layer: &mut Layer, c: usize) {
+    let maximal = g.common_right(&layer.bicliques[c].left);
+    for x in g.common_left(&maximal).iter() {
+        layer.add_left(g, c, x);
+    }
+}
+
+fn right_maximal(g: &Bigraph, layer: &mut Layer, c: usize) {
+    let maximal = g.common_left(&layer.bicliques[c].right);
+    for y in g.common_right(&maximal).iter() {
+        layer.add_right(g, c, y);
+    }
+}
 
-        if containment.start_layer(&new_layer.bicliques) {
-            iterate_sat(g, containment, new_layer, f)?;
+fn restrict_layer(g: &Bigraph, layer: &mut Layer) -> Result<(), ()> {
+    // Extending bicliques in a partition would cause them to overlap.
+    if layer.mode == Mode::Cover {
+        for c in mem::take(&mut layer.changed) {
+            right_maximal(g, layer, c);
+            left_maximal(g, layer, c);
         }
     }
+    layer.changed.clear();
+    layer.forced_updates(g)
+}
+
+/// Whether `cliques` are distinct maximal bicliques.
+fn is_distinct_maximal(g: &Bigraph, cliques: &[Biclique]) -> bool {
+    if !cliques.iter().all(|c| !c.is_empty() && g.is_maximal(c)) {
+        return false;
+    }
 
-    containment.finish_layer(g, layer.bicliques.clone());
-    f(BicliqueCover::new(g, layer.bicliques.clone()))
+    let cliques = sorted(cliques.to_vec());
+    cliques.windows(2).all(|w| w[0] != w[1])
 }
 
-fn left_maximal(g: &Bigraph, layer: &mut Layer, c: usize) {
-    let mut maximal: TBitSet<u32> = (0..g.right).collect();
-    for x in layer.bicliques[c].left.iter() {
-        for y in 0..g.right {
-            if !g.get(Edge(x, y)) {
-                maximal.remove(y)
+/// The maximal bicliques containing `clique`, if `g` has optional entries.
+///
+/// The search only closes bicliques over edges, so with optional entries a
+/// biclique may have several maximal supersets. Without optional entries,
+/// the search already finds the covers with all of them.
+fn maximal_supersets(g: &Bigraph, clique: &Biclique) -> Vec<Biclique> {
+    if !g.has_optional() {
+        return Vec::new();
+    }
+
+    let rows: Vec<u32> = (0..g.left)
+        .filter(|&x| !clique.left.get(x) && clique.right.iter().all(|y| g.allows(Edge(x, y))))
+        .collect();
+    let columns: Vec<u32> = (0..g.right)
+        .filter(|&y| !clique.right.get(y) && clique.left.iter().all(|x| g.allows(Edge(x, y))))
+        .collect();
+
+    // The added rows and columns have to form a biclique as well.
+    let mut added = Bigraph::new(rows.len() as u32, columns.len() as u32);
+    for (i, &x) in rows.iter().enumerate() {
+        for (j, &y) in columns.iter().enumerate() {
+            if g.allows(Edge(x, y)) {
+                added.add(Edge(i as u32, j as u32));
             }
         }
     }
 
-    'left: for x in 0..g.left {
-        for y in maximal.iter() {
-            if !g.get(Edge(x, y)) {
-                continue 'left;
+    let only_rows = Biclique {
+        left: (0..rows.len() as u32).collect(),
+        right: TBitSet::new(),
+    };
+    let only_columns = Biclique {
+        left: TBitSet::new(),
+        right: (0..columns.len() as u32).collect(),
+    };
+    let mut supersets: Vec<Biclique> = maximal_bicliques(&added)
+        .chain([only_rows, only_columns])
+        .map(|c| {
+            let mut superset = clique.clone();
+            for i in c.left.iter() {
+                superset.left.add(rows[i as usize]);
             }
-        }
+            for j in c.right.iter() {
+                superset.right.add(columns[j as usize]);
+            }
+            superset
+        })
+        .filter(|c| g.is_maximal(c))
+        .collect();
+    biclique_sort(&mut supersets);
+    supersets.dedup();
+    supersets
+}
 
-        layer.add_left(g, c, x);
+/// The ways to make the bicliques of a covering layer distinct and
+/// maximal, see [`BicliqueCovers::maximal_only`].
+fn maximal_extensions(g: &Bigraph, cliques: &[Biclique]) -> Vec<Box<[Biclique]>> {
+    let mut covers = vec![Vec::new()];
+    for clique in cliques {
+        let mut options = if g.is_maximal(clique) {
+            vec![clique.clone()]
+        } else {
+            maximal_supersets(g, clique)
+        };
+        // Just like in `count_covers`, every biclique has to contain an edge.
+        options.retain(|c| {
+            c.left
+                .iter()
+                .any(|x| c.right.iter().any(|y| g.get(Edge(x, y))))
+        });
+
+        covers = covers
+            .into_iter()
+            .flat_map(|cover| {
+                options.iter().map(move |c| {
+                    let mut cover = cover.clone();
+                    cover.push(c.clone());
+                    cover
+                })
+            })
+            .collect();
     }
+
+    covers
+        .into_iter()
+        .filter(|cover| is_distinct_maximal(g, cover))
+        .map(Vec::into_boxed_slice)
+        .collect()
 }
 
-fn right_maximal(g: &Bigraph, layer: &mut Layer, c: usize) {
-    let mut maximal: TBitSet<u32> = (0..g.left).collect();
-    for y in layer.bicliques[c].right.iter() {
-        for x in 0..g.left {
-            if !g.get(Edge(x, y)) {
-                maximal.remove(x)
-            }
-        }
+/// Whether some biclique of `layer` can't become maximal anymore, as there
+/// are no edges left which may be added to it. Only bicliques which have
+/// been closed by [`restrict_layer`] can be stuck.
+///
+/// With optional entries, any biclique may still become maximal once the
+/// layer covers the graph, see [`maximal_extensions`].
+fn has_stuck_biclique(g: &Bigraph, layer: &Layer) -> bool {
+    if g.has_optional() {
+        return false;
     }
 
-    'right: for y in 0..g.right {
-        for x in maximal.iter() {
-            if !g.get(Edge(x, y)) {
-                continue 'right;
-            }
+    let k = layer.bicliques.len();
+    let mut candidates: Vec<usize> = layer
+        .cliques()
+        .filter(|&c| {
+            let clique = &layer.bicliques[c];
+            !layer.changed.get(c) && (clique.is_empty() || !g.is_maximal(clique))
+        })
+        .collect();
+
+    for e in g.entries() {
+        if candidates.is_empty() {
+            return false;
         }
 
-        layer.add_right(g, c, y);
+        let index = Layer::index(g, k, e);
+        candidates.retain(|&c| !layer.data.get(index.may_add(c)));
     }
+
+    !candidates.is_empty()
 }
 
-fn restrict_layer(g: &Bigraph, layer: &mut Layer) -> Result<(), ()> {
-    for c in mem::take(&mut layer.changed) {
-        right_maximal(g, layer, c);
-        left_maximal(g, layer, c);
-    }
-    layer.changed.clear();
-    layer.forced_updates(g)
+#[derive(Debug, Clone)]
+struct Frame {
+    layer: Layer,
+    /// Whether `layer` already covers the whole graph, in which case we
+    /// only enumerate the ways its bicliques can still be extended.
+    sat: bool,
 }
 
-pub(crate) fn iterate<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
-    g: &Bigraph,
-    max_size: usize,
+/// The search for the covers of a single bigraph.
+///
+/// The search state is stored explicitly, so it can be resumed at any point.
+#[derive(Clone)]
+struct Search {
+    /// The bigraph we actually search in, with the covers
+    /// getting lifted back once we've found them.
+    kernel: Kernel,
+    mode: Mode,
     forced: Vec<Edge>,
-    mut f: F,
-) -> ControlFlow<T> {
-    for k in forced.len()..=max_size {
-        let layer = Layer::initial(g, k, &forced);
-        let mut containment = Containment::init(&layer.bicliques);
-        let mut stack = vec![layer];
-        'cliques: while let Some(layer) = stack.last_mut() {
-            match layer.forced_updates(g) {
+    max_size: usize,
+    /// The cover size to search for once `stack` is empty.
+    next_k: usize,
+    /// A lower bound for the size of the smallest cover.
+    lower: usize,
+    /// The size of the smallest cover found so far.
+    smallest: Option<usize>,
+    containment: Containment,
+    stack: Vec<Frame>,
+    /// Only emit covers consisting of distinct maximal bicliques, see [`BicliqueCovers::maximal_only`].
+    maximal: bool,
+    /// The covers emitted for the current size if `maximal` is set, sorted but
+    /// not lifted. If symmetric covers are skipped, these are the canonical
+    /// forms of their orbits instead, see [`Symmetries::canonical`].
+    emitted: HashSet<Box<[Biclique]>>,
+    /// The automorphisms of the kernel used to skip symmetric layers,
+    /// empty unless `symmetry` asks for them.
+    symmetries: Symmetries,
+    symmetry: Symmetry,
+    /// Covers which have been found but not emitted yet, e.g. the rest of an orbit.
+    pending: Vec<Box<[Biclique]>>,
+}
+
+impl Search {
+    fn new(g: &Bigraph, mode: Mode, max_size: usize, monitor: &Monitor) -> Search {
+        let kernel = Kernel::new(g, mode == Mode::Cover);
+        let fooling_set =
+            forced::fooling_set_with_budget(&kernel.graph, monitor.fooling_set_budget());
+        let forced = fooling_set.edges().to_vec();
+        let containment = match mode {
+            Mode::Cover => Containment::new(monitor.limits.max_containment_entries),
+            // Pruning layers based on their containment relies on
+            // the fact that bicliques in a cover may be extended.
+            Mode::Partition => Containment::disabled(),
+        };
+
+        Search {
+            kernel,
+            mode,
+            next_k: forced.len(),
+            lower: forced.len(),
+            smallest: None,
+            forced,
+            max_size,
+            containment,
+            stack: Vec::new(),
+            maximal: false,
+            emitted: HashSet::new(),
+            symmetries: Symmetries::default(),
+            symmetry: Symmetry::Ignore,
+            pending: Vec::new(),
+        }
+    }
+
+    /// See [`BicliqueCovers::with_symmetry`].
+    fn set_symmetry(&mut self, symmetry: Symmetry) {
+        self.symmetry = symmetry;
+        self.symmetries = match symmetry {
+            Symmetry::Ignore => Symmetries::default(),
+            Symmetry::Representatives | Symmetry::Orbits => Symmetries::new(&self.kernel),
+        };
+    }
+
+    /// Discards the current state and only searches for covers with `k` bicliques.
+    fn restart_at(&mut self, k: usize) {
+        self.stack.clear();
+        self.containment.clear();
+        self.emitted.clear();
+        self.pending.clear();
+        self.next_k = k.max(self.forced.len());
+        self.max_size = k;
+    }
+
+    /// Starts searching for covers of the next size once the current
+    /// one is done, returning `false` if it would exceed `max_size`.
+    fn start_level(&mut self, stats: &mut SearchStats) -> bool {
+        if self.smallest.is_none() {
+            self.lower = self.next_k;
+        }
+
+        if self.next_k > self.max_size {
+            return false;
+        }
+
+        let layer = Layer::initial(&self.kernel.graph, self.mode, self.next_k, &self.forced);
+        self.containment.reinit(&layer.bicliques);
+        self.emitted.clear();
+        self.stack.push(Frame { layer, sat: false });
+        stats.layers += 1;
+        self.next_k += 1;
+        true
+    }
+
+    /// Looks for the next way to extend the bicliques of the covering layer
+    /// at the top of the stack, emitting its cover once there are none left.
+    fn step_sat(&mut self, stats: &mut SearchStats) -> Option<Box<[Biclique]>> {
+        let g = &self.kernel.graph;
+        while let Some(mut new_layer) = self.stack.last_mut().unwrap().layer.guess_edge(g) {
+            stats.branches += 1;
+            match restrict_layer(g, &mut new_layer) {
                 Ok(()) => (),
                 Err(()) => {
-                    containment.finish_layer(g, stack.pop().unwrap().bicliques);
-                    continue 'cliques;
+                    stats.failed_updates += 1;
+                    continue;
                 }
             }
 
-            match restrict_layer(g, layer) {
-                Ok(()) => (),
-                Err(()) => {
-                    containment.finish_layer(g, stack.pop().unwrap().bicliques);
-                    continue 'cliques;
+            if self.maximal && has_stuck_biclique(g, &new_layer)
+                || self
+                    .containment
+                    .should_discard(&new_layer.bicliques, &mut self.symmetries)
+            {
+                stats.discarded += 1;
+                continue;
+            }
+
+            self.containment.enter_layer(&new_layer.bicliques);
+            self.stack.push(Frame {
+                layer: new_layer,
+                sat: true,
+            });
+            stats.layers += 1;
+            return None;
+        }
+
+        let layer = self.stack.pop().unwrap().layer;
+        self.containment.finish_layer(g, layer.bicliques.clone());
+        // Covers with empty bicliques have already been emitted when searching
+        // for smaller ones, unless they can be filled with maximal bicliques.
+        if layer.bicliques.iter().any(|c| c.is_empty()) && !(self.maximal && g.has_optional()) {
+            return None;
+        }
+
+        let covers = if self.maximal {
+            maximal_extensions(g, &layer.bicliques)
+        } else {
+            vec![layer.bicliques]
+        };
+        for cliques in covers {
+            for cliques in self.new_covers(&cliques) {
+                self.pending.push(self.kernel.lift_all(&cliques));
+            }
+        }
+        self.pending.pop()
+    }
+
+    /// The covers to emit for a covering layer with the bicliques `cliques`.
+    ///
+    /// These are its images under all automorphisms if `symmetry` asks for
+    /// them, leaving out the ones which have already been emitted if needed.
+    fn new_covers(&mut self, cliques: &[Biclique]) -> Vec<Box<[Biclique]>> {
+        let cover = match self.symmetry {
+            Symmetry::Ignore if !self.maximal => return vec![cliques.into()],
+            _ => sorted(cliques.to_vec()),
+        };
+
+        // Orbits are either emitted as a whole or not at all,
+        // so we only have to remember one cover of each.
+        let key = match self.symmetry {
+            Symmetry::Ignore => cover.clone(),
+            Symmetry::Representatives | Symmetry::Orbits => self.symmetries.canonical(&cover),
+        };
+        if !self.emitted.insert(key) {
+            return Vec::new();
+        }
+
+        match self.symmetry {
+            Symmetry::Orbits => self.symmetries.orbit(&cover),
+            Symmetry::Ignore | Symmetry::Representatives => vec![cover],
+        }
+    }
+
+    /// Propagates the layer at the top of the stack and
+    /// guesses an edge if it does not yet cover the graph.
+    fn step(&mut self, stats: &mut SearchStats) {
+        let g = &self.kernel.graph;
+        let layer = &mut self.stack.last_mut().unwrap().layer;
+        let discard = if layer.forced_updates(g).is_err() || restrict_layer(g, layer).is_err() {
+            stats.failed_updates += 1;
+            true
+        } else if self.maximal && has_stuck_biclique(g, layer)
+            || self
+                .containment
+                .should_discard(&layer.bicliques, &mut self.symmetries)
+        {
+            stats.discarded += 1;
+            true
+        } else {
+            false
+        };
+
+        if discard {
+            let layer = self.stack.pop().unwrap().layer;
+            self.containment.finish_layer(g, layer.bicliques);
+        } else if layer.covers(g) {
+            self.stack.last_mut().unwrap().sat = true;
+        } else {
+            while let Some(new_layer) = self.stack.last_mut().unwrap().layer.guess_edge(g) {
+                stats.branches += 1;
+                if self.containment.start_layer(&new_layer.bicliques) {
+                    self.stack.push(Frame {
+                        layer: new_layer,
+                        sat: false,
+                    });
+                    stats.layers += 1;
+                    return;
                 }
+                stats.discarded += 1;
             }
 
-            if containment.should_discard(&layer.bicliques) {
-                containment.finish_layer(g, stack.pop().unwrap().bicliques);
-                continue 'cliques;
+            let layer = self.stack.pop().unwrap().layer;
+            self.containment.finish_layer(g, layer.bicliques);
+        }
+    }
+
+    /// Continues the search for covers of the current size,
+    /// returning `None` once there are none left or `monitor` is exhausted.
+    fn next_of_size(&mut self, monitor: &mut Monitor) -> Option<Box<[Biclique]>> {
+        if self.stack.is_empty() && self.pending.is_empty() {
+            return None;
+        }
+
+        let start = Instant::now();
+        let cliques = self.advance(monitor);
+        monitor.stats.record_time(self.next_k - 1, start.elapsed());
+        cliques
+    }
+
+    fn advance(&mut self, monitor: &mut Monitor) -> Option<Box<[Biclique]>> {
+        loop {
+            if let Some(cliques) = self.pending.pop() {
+                let size = cliques.iter().filter(|c| !c.is_empty()).count();
+                self.smallest = Some(self.smallest.map_or(size, |s| s.min(size)));
+                return Some(cliques);
             }
 
-            if layer.covers(g) {
-                iterate_sat(g, &mut containment, stack.pop().unwrap(), &mut f)?;
-            } else {
-                while let Some(new_layer) = layer.guess_edge(g) {
-                    if containment.start_layer(&new_layer.bicliques) {
-                        stack.push(new_layer);
-                        continue 'cliques;
-                    }
+            let frame = self.stack.last()?;
+            if !monitor.step() {
+                return None;
+            }
+
+            let stats = &mut monitor.stats;
+            if frame.sat {
+                if let Some(cliques) = self.step_sat(stats) {
+                    self.pending.push(cliques);
                 }
+            } else {
+                self.step(stats);
+            }
+
+            stats.containment_entries = stats.containment_entries.max(self.containment.len());
+        }
+    }
+
+    /// Stops once all covers of the current size have been found.
+    fn finish_level(&mut self) {
+        self.max_size = self.next_k - 1;
+    }
 
-                containment.finish_layer(g, stack.pop().unwrap().bicliques);
+    fn next(&mut self, monitor: &mut Monitor) -> Option<Box<[Biclique]>> {
+        loop {
+            if let Some(cliques) = self.next_of_size(monitor) {
+                return Some(cliques);
+            } else if monitor.exhausted || !self.start_level(&mut monitor.stats) {
+                return None;
             }
         }
     }
+}
+
+/// Looks for covers of `g` with fewer bicliques than `best`, but at least `lower` of them.
+///
+/// Returns the smallest cover found together with a lower bound for the size of
+/// the minimum cover, which is the size of that cover unless `monitor` got exhausted.
+fn shrink(
+    g: &Bigraph,
+    mut best: Box<[Biclique]>,
+    lower: usize,
+    monitor: &mut Monitor,
+) -> (Box<[Biclique]>, usize) {
+    let mut search = Search::new(g, Mode::Cover, best.len(), monitor);
+    let mut lower = lower.max(search.forced.len());
+    while best.len() > lower {
+        search.restart_at(best.len() - 1);
+        match search.next(monitor) {
+            Some(found) => best = found.iter().filter(|c| !c.is_empty()).cloned().collect(),
+            None if monitor.exhausted => break,
+            None => lower = best.len(),
+        }
+    }
 
-    ControlFlow::Continue(())
+    (best, lower)
+}
+
+/// Like [`shrink`], used to repair covers after editing a bigraph, see [`IncrementalCover`].
+pub(crate) fn shrink_cover(
+    g: &Bigraph,
+    best: Box<[Biclique]>,
+    lower: usize,
+    limits: SearchLimits,
+) -> (Box<[Biclique]>, usize) {
+    shrink(g, best, lower, &mut Monitor::new(limits))
+}
+
+/// Computes a minimum biclique cover of `g`, covering each connected component separately.
+///
+/// Instead of searching for covers of increasing size, we start with the
+/// cover found by [`heuristic_cover`] and look for smaller ones until there
+/// are none left, which avoids searching sizes below a weak lower bound.
+pub(crate) fn minimum_cover(g: &Bigraph, limits: SearchLimits) -> (Box<[Biclique]>, Completion) {
+    let mut monitor = Monitor::new(limits);
+    let mut cliques = Vec::new();
+    let (mut lower_bound, mut upper_bound) = (0, 0);
+    for (component, map) in g.components() {
+        let best = heuristic_cover(&component, HeuristicOptions::default())
+            .cliques()
+            .into();
+        let (best, lower) = shrink(&component, best, 0, &mut monitor);
+        lower_bound += lower;
+        upper_bound += best.len();
+        cliques.extend(best.iter().map(|c| c.lift(&map)));
+    }
+
+    let completion = if monitor.exhausted {
+        Completion::CutOff {
+            lower_bound,
+            upper_bound: Some(upper_bound),
+        }
+    } else {
+        Completion::Complete
+    };
+    (cliques.into_boxed_slice(), completion)
+}
+
+enum Inner {
+    Single(Box<Search>),
+    Components(Product),
+}
+
+/// An iterator over the biclique covers of a graph, see [`biclique_covers`].
+///
+/// The search state is stored explicitly, so it can be resumed at any point.
+pub struct BicliqueCovers<'g> {
+    g: &'g Bigraph,
+    mode: Mode,
+    inner: Inner,
+    monitor: Monitor<'g>,
+    minimum_only: bool,
+}
+
+impl<'g> BicliqueCovers<'g> {
+    /// Iterates over the biclique covers of `g` with at most `max_size` elements,
+    /// starting with the smallest ones.
+    ///
+    /// Identical rows and columns are merged before searching, so they always end
+    /// up in the same bicliques and covers which split them up aren't yielded.
+    /// This never leaves out a cover made of maximal bicliques, as these never
+    /// split identical rows or columns, see [`BicliqueCovers::maximal_only`].
+    pub fn new(g: &'g Bigraph, max_size: usize) -> BicliqueCovers<'g> {
+        BicliqueCovers::with_limits(g, max_size, SearchLimits::default())
+    }
+
+    /// Like [`BicliqueCovers::new`], but stops early once one of the `limits` is reached.
+    /// Use [`BicliqueCovers::completion`] to check whether that happened.
+    pub fn with_limits(
+        g: &'g Bigraph,
+        max_size: usize,
+        limits: SearchLimits,
+    ) -> BicliqueCovers<'g> {
+        BicliqueCovers::with_mode(g, Mode::Cover, max_size, limits)
+    }
+
+    /// Iterates over the biclique partitions of `g` with at most `max_size` elements,
+    /// starting with the smallest ones. See [`biclique_partitions`].
+    pub fn partitions(g: &'g Bigraph, max_size: usize) -> BicliqueCovers<'g> {
+        BicliqueCovers::with_mode(g, Mode::Partition, max_size, SearchLimits::default())
+    }
+
+    fn with_mode(
+        g: &'g Bigraph,
+        mode: Mode,
+        max_size: usize,
+        limits: SearchLimits,
+    ) -> BicliqueCovers<'g> {
+        let monitor = Monitor::new(limits);
+        let components = g.components();
+        let inner = if components.len() > 1 {
+            Inner::Components(Product::new(components, mode, max_size, &monitor))
+        } else {
+            Inner::Single(Box::new(Search::new(g, mode, max_size, &monitor)))
+        };
+
+        BicliqueCovers {
+            g,
+            mode,
+            inner,
+            monitor,
+            minimum_only: false,
+        }
+    }
+
+    /// Only yields covers consisting of distinct maximal bicliques, each of them once.
+    ///
+    /// Layers which can't be extended to such a cover anymore get discarded during
+    /// the search. Bicliques are closed over optional entries as well, so these
+    /// are the covers counted by [`count_covers`]. Doesn't affect partitions
+    /// and should be called before iterating.
+    pub fn maximal_only(mut self) -> BicliqueCovers<'g> {
+        if self.mode == Mode::Cover {
+            match &mut self.inner {
+                Inner::Single(search) => search.maximal = true,
+                Inner::Components(product) => product.maximal_only(),
+            }
+        }
+        self
+    }
+
+    /// Skips parts of the search which are symmetric to ones which have already
+    /// been explored, using the [automorphisms](Bigraph::automorphisms) of `g`.
+    ///
+    /// With [`Symmetry::Representatives`], only a single cover of each orbit gets
+    /// yielded, otherwise the orbits of the covers found are expanded again.
+    /// Each connected component is handled separately, so automorphisms swapping
+    /// components aren't taken into account. Doesn't affect partitions and should
+    /// be called before iterating.
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> BicliqueCovers<'g> {
+        if self.mode == Mode::Cover {
+            match &mut self.inner {
+                Inner::Single(search) => search.set_symmetry(symmetry),
+                Inner::Components(product) => product.set_symmetry(symmetry),
+            }
+        }
+        self
+    }
+
+    /// Stops once all covers with the size of the smallest one have been yielded.
+    pub fn minimum_only(mut self) -> BicliqueCovers<'g> {
+        self.minimum_only = true;
+        self
+    }
+
+    /// Whether the search has been cut off by its [`SearchLimits`]
+    /// before finding all covers. Only meaningful once the
+    /// iterator has returned `None`.
+    pub fn completion(&self) -> Completion {
+        if !self.monitor.exhausted {
+            return Completion::Complete;
+        }
+
+        let (lower_bound, upper_bound) = match &self.inner {
+            Inner::Single(search) => (search.lower, search.smallest),
+            Inner::Components(product) => product.bounds(),
+        };
+        Completion::CutOff {
+            lower_bound,
+            upper_bound,
+        }
+    }
+
+    /// The statistics of the search so far.
+    pub fn stats(&self) -> &SearchStats {
+        &self.monitor.stats
+    }
+
+    /// Calls `f` with the current statistics roughly every `interval` while searching.
+    pub fn with_progress<F: FnMut(&SearchStats) + 'g>(
+        mut self,
+        interval: Duration,
+        f: F,
+    ) -> BicliqueCovers<'g> {
+        self.monitor.set_progress(interval, Box::new(f));
+        self
+    }
+}
+
+impl Iterator for BicliqueCovers<'_> {
+    type Item = BicliqueCover;
+
+    fn next(&mut self) -> Option<BicliqueCover> {
+        let cliques = match &mut self.inner {
+            Inner::Single(search) => search.next(&mut self.monitor)?,
+            Inner::Components(product) => product.next(&mut self.monitor)?,
+        };
+
+        if self.minimum_only {
+            match &mut self.inner {
+                Inner::Single(search) => search.finish_level(),
+                Inner::Components(product) => product.finish_size(),
+            }
+        }
+
+        Some(match self.mode {
+            Mode::Cover => BicliqueCover::new(self.g, cliques),
+            Mode::Partition => BicliqueCover::new_partition(self.g, cliques),
+        })
+    }
 }
@@ -0,0 +1,104 @@
+use super::SearchLimits;
+use crate::forced::Budget;
+use std::time::{Duration, Instant};
+
+/// Counters collected while searching for biclique covers, see [`BicliqueCovers::stats`](super::BicliqueCovers::stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// The number of layers pushed onto the search stack.
+    pub layers: u64,
+    /// The number of layers which turned out to be inconsistent
+    /// while propagating their forced updates.
+    pub failed_updates: u64,
+    /// The number of layers created by guessing an edge.
+    pub branches: u64,
+    /// The number of layers which didn't need to be explored, because
+    /// they're contained in a layer which has already been explored, possibly
+    /// after applying an automorphism, or because one of their bicliques can't
+    /// become maximal anymore while only searching for maximal covers.
+    pub discarded: u64,
+    /// The largest number of explored layers remembered at the same time.
+    pub containment_entries: usize,
+    /// `time_per_size[k]` is the time spent searching for covers with `k` bicliques.
+    pub time_per_size: Vec<Duration>,
+}
+
+impl SearchStats {
+    pub(super) fn record_time(&mut self, k: usize, time: Duration) {
+        if self.time_per_size.len() <= k {
+            self.time_per_size.resize(k + 1, Duration::ZERO);
+        }
+        self.time_per_size[k] += time;
+    }
+}
+
+struct Progress<'a> {
+    interval: Duration,
+    last: Instant,
+    f: Box<dyn FnMut(&SearchStats) + 'a>,
+}
+
+/// Keeps track of the limits and statistics of a search.
+pub(super) struct Monitor<'a> {
+    pub(super) limits: SearchLimits,
+    pub(super) stats: SearchStats,
+    pub(super) exhausted: bool,
+    steps: u64,
+    progress: Option<Progress<'a>>,
+}
+
+impl<'a> Monitor<'a> {
+    pub(super) fn new(limits: SearchLimits) -> Monitor<'a> {
+        Monitor {
+            limits,
+            stats: SearchStats::default(),
+            exhausted: false,
+            steps: 0,
+            progress: None,
+        }
+    }
+
+    pub(super) fn set_progress(
+        &mut self,
+        interval: Duration,
+        f: Box<dyn FnMut(&SearchStats) + 'a>,
+    ) {
+        self.progress = Some(Progress {
+            interval,
+            last: Instant::now(),
+            f,
+        });
+    }
+
+    /// Accounts for a step of the search, returning `false` if
+    /// we're out of budget.
+    pub(super) fn step(&mut self) -> bool {
+        self.steps += 1;
+        self.exhausted |= self
+            .limits
+            .max_layers
+            .is_some_and(|max| self.stats.layers > max);
+
+        // Checking the time is comparatively expensive.
+        if (self.steps - 1).is_multiple_of(1024) {
+            let now = Instant::now();
+            self.exhausted |= self.limits.deadline.is_some_and(|deadline| now >= deadline);
+            if let Some(progress) = &mut self.progress {
+                if now.duration_since(progress.last) >= progress.interval {
+                    progress.last = now;
+                    (progress.f)(&self.stats);
+                }
+            }
+        }
+
+        !self.exhausted
+    }
+
+    /// The budget for computing the fooling set at the start of the search.
+    pub(super) fn fooling_set_budget(&self) -> Budget {
+        match self.limits.deadline {
+            Some(deadline) => Budget::Deadline(deadline),
+            None => Budget::Exact,
+        }
+    }
+}
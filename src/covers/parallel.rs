@@ -0,0 +1,338 @@
+//! Searching for biclique covers using multiple threads.
+//!
+//! The plain search hands out each cover size of each connected component
+//! as a whole, as these are searched independently anyway.
+//!
+//! The search for maximal covers splits up the sizes further. Each worker explores whole subtrees of the search on its own, using its own
+//! [`Containment`] which it keeps between subtrees. Discarding a layer contained
+//! in one which has already been explored stays sound no matter which worker
+//! explored it, as all covers end up in the same place, as long as the explored
+//! layer comes first in a depth-first search, see [`Containment::start_root`].
+//! Whenever a worker runs out of work, the busy ones split off the next branch
+//! of the lowest layer on their stack, which tends to be the largest remaining subtree.
+use super::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::{mpsc, Condvar, Mutex};
+use std::thread;
+use std::{iter, mem};
+
+/// Options for [`par_biclique_covers`] and [`par_maximal_covers`](crate::par_maximal_covers).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParallelOptions {
+    /// The number of worker threads, `0` uses the available parallelism.
+    pub threads: usize,
+    /// Pass the covers to the callback ordered by size and then by their bicliques.
+    ///
+    /// Otherwise [`par_maximal_covers`](crate::par_maximal_covers) passes covers on as soon as they have
+    /// been found, and [`par_biclique_covers`] only orders them by size.
+    pub deterministic: bool,
+}
+
+/// A subtree which still has to be explored.
+struct Task {
+    root: Frame,
+    /// The position of `root` in the search tree.
+    path: Box<[u32]>,
+}
+
+struct Queue {
+    tasks: Vec<Task>,
+    /// The number of workers waiting for a task.
+    idle: usize,
+    done: bool,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar,
+    /// Whether there are more idle workers than tasks,
+    /// checked by the busy workers without taking the lock.
+    hungry: AtomicBool,
+    /// The layers explored by all workers, for each cover size as only
+    /// layers with the same number of bicliques can be compared.
+    explored: Mutex<HashMap<usize, Containment>>,
+    stop: AtomicBool,
+    threads: usize,
+}
+
+impl Shared {
+    fn update_hungry(&self, queue: &Queue) {
+        let hungry = queue.tasks.len() < queue.idle;
+        self.hungry.store(hungry, atomic::Ordering::Relaxed);
+    }
+
+    fn push(&self, task: Task) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.tasks.push(task);
+        self.update_hungry(&queue);
+        self.available.notify_one();
+    }
+
+    /// Waits for the next task, returning `None` once all workers are out of work.
+    fn pop(&self) -> Option<Task> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if queue.done || self.stop.load(atomic::Ordering::Relaxed) {
+                return None;
+            }
+
+            if let Some(task) = queue.tasks.pop() {
+                self.update_hungry(&queue);
+                return Some(task);
+            }
+
+            queue.idle += 1;
+            if queue.idle == self.threads {
+                queue.done = true;
+                self.available.notify_all();
+                return None;
+            }
+
+            self.update_hungry(&queue);
+            queue = self.available.wait(queue).unwrap();
+            queue.idle -= 1;
+        }
+    }
+
+    fn stop(&self) {
+        self.stop.store(true, atomic::Ordering::Relaxed);
+        let _queue = self.queue.lock().unwrap();
+        self.available.notify_all();
+    }
+}
+
+impl Search {
+    /// Splits off the next branch of the lowest layer on the stack which still has one.
+    fn donate(&mut self, shared: &Shared) {
+        let g = &self.kernel.graph;
+        let top = self.stack.len() - 1;
+        for (depth, frame) in self.stack[..top].iter_mut().enumerate() {
+            while let Some(mut layer) = frame.layer.guess_edge(g) {
+                // See `step_sat`.
+                if frame.sat && restrict_layer(g, &mut layer).is_err() {
+                    continue;
+                }
+
+                let path = self.containment.split_off(depth);
+                shared.push(Task {
+                    root: Frame {
+                        layer,
+                        sat: frame.sat,
+                    },
+                    path,
+                });
+                return;
+            }
+        }
+    }
+}
+
+/// How many covers a worker collects before passing them on,
+/// waking up the receiving thread for every cover is rather slow.
+const BATCH_SIZE: usize = 64;
+
+type Batch = Vec<Box<[Biclique]>>;
+
+fn work(base: &Search, shared: &Shared, sender: mpsc::Sender<Batch>) {
+    let mut search = base.clone();
+    let mut stats = SearchStats::default();
+    let mut batch = Vec::new();
+    while let Some(Task { root, path }) = shared.pop() {
+        let k = root.layer.bicliques.len();
+        search.containment = shared
+            .explored
+            .lock()
+            .unwrap()
+            .entry(k)
+            .or_insert_with(|| base.containment.clone())
+            .clone();
+        search.containment.start_root(&root.layer.bicliques, &path);
+        search.stack.push(root);
+        while let Some(frame) = search.stack.last() {
+            if shared.stop.load(atomic::Ordering::Relaxed) {
+                return;
+            }
+
+            if frame.sat {
+                // A covering layer may result in multiple covers.
+                batch.extend(search.step_sat(&mut stats));
+                batch.append(&mut search.pending);
+                if batch.len() >= BATCH_SIZE && sender.send(mem::take(&mut batch)).is_err() {
+                    return;
+                }
+            } else {
+                search.step(&mut stats);
+            }
+
+            if shared.hungry.load(atomic::Ordering::Relaxed) && search.stack.len() > 1 {
+                search.donate(shared);
+            }
+        }
+
+        let mut explored = shared.explored.lock().unwrap();
+        search.containment.share(explored.get_mut(&k).unwrap());
+        drop(explored);
+        if !batch.is_empty() && sender.send(mem::take(&mut batch)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Orders covers by their size and then by their bicliques.
+fn cover_cmp(a: &BicliqueCover, b: &BicliqueCover) -> Ordering {
+    let (a, b) = (a.cliques(), b.cliques());
+    a.len().cmp(&b.len()).then_with(|| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| biclique_cmp(a, b))
+            .find(|&ord| ord != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+fn emit<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    receiver: mpsc::Receiver<Batch>,
+    deterministic: bool,
+    mut f: F,
+) -> ControlFlow<T> {
+    if deterministic {
+        let mut covers: Vec<_> = receiver
+            .into_iter()
+            .flatten()
+            .map(|cliques| BicliqueCover::new(g, cliques))
+            .collect();
+        covers.sort_by(cover_cmp);
+        covers.dedup();
+        covers.into_iter().try_for_each(f)
+    } else {
+        // Workers exploring neighboring subtrees at the same time can't discard the
+        // layers of each other, so they may both find the same cover. Unlike the
+        // sequential search, this has to remember every cover passed on.
+        let mut seen = HashSet::new();
+        for cliques in receiver.into_iter().flatten() {
+            let cover = BicliqueCover::new(g, cliques);
+            if seen.insert(cover.clone()) {
+                f(cover)?;
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+fn thread_count(options: ParallelOptions) -> usize {
+    match options.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+/// Calls `f` with each of `tasks` on up to `threads` threads, starting with
+/// the first ones, and returns the results in the order of the tasks.
+pub(super) fn par_map<I: Send, O: Send, F: Fn(I) -> O + Sync>(
+    threads: usize,
+    tasks: Vec<I>,
+    f: F,
+) -> Vec<O> {
+    let n = tasks.len();
+    let queue = Mutex::new(tasks.into_iter().enumerate().rev().collect::<Vec<_>>());
+    let results = Mutex::new((0..n).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.min(n) {
+            scope.spawn(|| loop {
+                let Some((i, task)) = queue.lock().unwrap().pop() else {
+                    return;
+                };
+                let result = f(task);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(Option::unwrap)
+        .collect()
+}
+
+pub(crate) fn par_covers<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    options: ParallelOptions,
+    f: F,
+) -> ControlFlow<T> {
+    let mut monitor = Monitor::new(SearchLimits::default());
+    let mut product = Product::new(g.components(), Mode::Cover, max_size, &monitor);
+    product.fill_parallel(thread_count(options));
+
+    // Nothing is left to search, so this just combines the covers of the components.
+    let mut covers =
+        iter::from_fn(|| product.next(&mut monitor)).map(|cliques| BicliqueCover::new(g, cliques));
+    if options.deterministic {
+        let mut covers: Vec<_> = covers.collect();
+        covers.sort_by(cover_cmp);
+        covers.into_iter().try_for_each(f)
+    } else {
+        covers.try_for_each(f)
+    }
+}
+
+pub(crate) fn par_maximal_covers<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    options: ParallelOptions,
+    f: F,
+) -> ControlFlow<T> {
+    let threads = thread_count(options);
+    let mut base = Search::new(
+        g,
+        Mode::Cover,
+        max_size,
+        &Monitor::new(SearchLimits::default()),
+    );
+    // Which other covers are found depends on how the work was split up
+    // between the threads, so restrict the output to the maximal ones.
+    base.maximal = true;
+    // Workers take tasks from the back, so start with the small covers.
+    let tasks = (base.forced.len()..=max_size)
+        .rev()
+        .map(|k| Task {
+            root: Frame {
+                layer: Layer::initial(&base.kernel.graph, Mode::Cover, k, &base.forced),
+                sat: false,
+            },
+            path: Box::new([]),
+        })
+        .collect();
+
+    let shared = Shared {
+        queue: Mutex::new(Queue {
+            tasks,
+            idle: 0,
+            done: false,
+        }),
+        available: Condvar::new(),
+        hungry: AtomicBool::new(false),
+        explored: Mutex::new(HashMap::new()),
+        stop: AtomicBool::new(false),
+        threads,
+    };
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..threads {
+            let (base, shared, sender) = (&base, &shared, sender.clone());
+            scope.spawn(move || work(base, shared, sender));
+        }
+        drop(sender);
+
+        let result = emit(g, receiver, options.deterministic, f);
+        shared.stop();
+        result
+    })
+}
@@ -0,0 +1,274 @@
+//! Combining the covers of the connected components of a bigraph.
+//!
+//! Every biclique is part of a single component, so the covers of a bigraph
+//! are exactly the unions of covers of its components. Searching each component
+//! on its own keeps the cost additive instead of multiplicative.
+use super::parallel::par_map;
+use super::{Mode, Monitor, Search, SearchLimits, Symmetry};
+use crate::*;
+use std::collections::HashSet;
+use std::iter;
+
+/// The covers of a single component, grouped by their size.
+struct Component {
+    map: IndexMap,
+    search: Search,
+    /// `covers[s]` contains the covers with `s` bicliques found so far,
+    /// already lifted to the whole bigraph.
+    covers: Vec<Vec<Box<[Biclique]>>>,
+    /// The covers in `covers`, to skip the ones found again.
+    seen: HashSet<Box<[Biclique]>>,
+    /// All covers with less than `complete` bicliques have been found.
+    complete: usize,
+}
+
+impl Component {
+    fn covers(&self, size: usize) -> &[Box<[Biclique]>] {
+        self.covers.get(size).map_or(&[], |covers| covers)
+    }
+
+    fn insert(&mut self, cliques: &[Biclique]) {
+        let mut cliques: Box<[Biclique]> = cliques.iter().map(|c| c.lift(&self.map)).collect();
+        biclique_sort(&mut cliques);
+        if self.covers.len() <= cliques.len() {
+            self.covers.resize(cliques.len() + 1, Vec::new());
+        }
+
+        if self.seen.insert(cliques.clone()) {
+            self.covers[cliques.len()].push(cliques);
+        }
+    }
+
+    /// Searches until all covers with at most `size` bicliques
+    /// have been found or `monitor` is exhausted.
+    fn fill(&mut self, size: usize, monitor: &mut Monitor) {
+        while self.complete <= size {
+            if let Some(cliques) = self.search.next_of_size(monitor) {
+                self.insert(&cliques);
+                continue;
+            } else if monitor.exhausted {
+                return;
+            }
+
+            self.complete = self.search.next_k;
+            if self.complete <= size && !self.search.start_level(&mut monitor.stats) {
+                self.complete = usize::MAX;
+            }
+        }
+    }
+
+    /// The size of the smallest cover of this component.
+    fn minimum(&mut self, monitor: &mut Monitor) -> Option<usize> {
+        (0..=self.search.max_size).find(|&size| {
+            self.fill(size, monitor);
+            !self.covers(size).is_empty()
+        })
+    }
+}
+
+/// A way to combine the covers of each component.
+struct Combination {
+    /// The size of the cover of each component.
+    split: Box<[usize]>,
+    /// The index of the cover of each component.
+    indices: Box<[usize]>,
+}
+
+/// The covers of a bigraph with multiple components, see the module documentation.
+pub(super) struct Product {
+    components: Vec<Component>,
+    max_size: usize,
+    /// The number of bicliques of the covers emitted next.
+    size: usize,
+    /// The remaining ways to split `size - 1` among the components, in reverse order.
+    splits: Vec<Box<[usize]>>,
+    /// The combination emitted next.
+    current: Option<Combination>,
+}
+
+impl Product {
+    pub(super) fn new(
+        components: Vec<(Bigraph, IndexMap)>,
+        mode: Mode,
+        max_size: usize,
+        monitor: &Monitor,
+    ) -> Product {
+        let components = components
+            .into_iter()
+            .map(|(g, map)| Component {
+                map,
+                search: Search::new(&g, mode, max_size, monitor),
+                covers: Vec::new(),
+                seen: HashSet::new(),
+                complete: 0,
+            })
+            .collect();
+
+        Product {
+            components,
+            max_size,
+            size: 0,
+            splits: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// All ways to split `size` among the components so that each of them has a cover
+    /// of the given size, in lexicographic order.
+    fn splits_of(&mut self, size: usize, monitor: &mut Monitor) -> Vec<Box<[usize]>> {
+        let mut minima = Vec::with_capacity(self.components.len());
+        for c in self.components.iter_mut() {
+            match c.minimum(monitor) {
+                Some(min) => minima.push(min),
+                None => return Vec::new(),
+            }
+        }
+
+        let total: usize = minima.iter().sum();
+        if size < total {
+            return Vec::new();
+        }
+
+        for (c, &min) in self.components.iter_mut().zip(&minima) {
+            c.fill(size - total + min, monitor);
+        }
+
+        fn extend(
+            components: &[Component],
+            split: &mut Vec<usize>,
+            remaining: usize,
+            splits: &mut Vec<Box<[usize]>>,
+        ) {
+            let Some((c, rest)) = components.split_first() else {
+                if remaining == 0 {
+                    splits.push(split.as_slice().into());
+                }
+                return;
+            };
+
+            for s in 0..=remaining {
+                if !c.covers(s).is_empty() {
+                    split.push(s);
+                    extend(rest, split, remaining - s, splits);
+                    split.pop();
+                }
+            }
+        }
+
+        let mut splits = Vec::new();
+        extend(&self.components, &mut Vec::new(), size, &mut splits);
+        splits
+    }
+
+    /// Searches all covers of the components which may be needed up front,
+    /// spreading the sizes of each component over `threads` threads.
+    ///
+    /// Finds the same covers as searching on demand, as the
+    /// sizes of a component are searched independently.
+    pub(super) fn fill_parallel(&mut self, threads: usize) {
+        let minima = par_map(threads, self.components.iter_mut().collect(), |c| {
+            c.minimum(&mut Monitor::new(SearchLimits::default()))
+        });
+        let Some(minima) = minima.into_iter().collect::<Option<Vec<_>>>() else {
+            return;
+        };
+
+        let total: usize = minima.iter().sum();
+        let mut levels = Vec::new();
+        for (i, (c, &min)) in self.components.iter().zip(&minima).enumerate() {
+            let largest = (self.max_size + min).saturating_sub(total);
+            levels.extend((min + 1..=largest).map(|k| (i, c.search.clone(), k)));
+        }
+
+        let covers = par_map(threads, levels, |(i, mut search, k)| {
+            let mut monitor = Monitor::new(SearchLimits::default());
+            search.restart_at(k);
+            (
+                i,
+                iter::from_fn(|| search.next(&mut monitor)).collect::<Vec<_>>(),
+            )
+        });
+        for (i, covers) in covers {
+            for cliques in covers {
+                self.components[i].insert(&cliques);
+            }
+        }
+
+        for c in &mut self.components {
+            c.complete = usize::MAX;
+        }
+    }
+
+    /// See [`BicliqueCovers::maximal_only`](super::BicliqueCovers::maximal_only).
+    pub(super) fn maximal_only(&mut self) {
+        for c in &mut self.components {
+            c.search.maximal = true;
+        }
+    }
+
+    /// See [`BicliqueCovers::with_symmetry`](super::BicliqueCovers::with_symmetry).
+    pub(super) fn set_symmetry(&mut self, symmetry: Symmetry) {
+        for c in &mut self.components {
+            c.search.set_symmetry(symmetry);
+        }
+    }
+
+    /// Stops once all covers with the size of the last one have been emitted.
+    pub(super) fn finish_size(&mut self) {
+        self.max_size = self.size - 1;
+    }
+
+    /// Bounds for the size of the smallest cover, see [`Completion::CutOff`](super::Completion::CutOff).
+    pub(super) fn bounds(&self) -> (usize, Option<usize>) {
+        let lower = self.components.iter().map(|c| c.search.lower).sum();
+        let upper = self.components.iter().map(|c| c.search.smallest).sum();
+        (lower, upper)
+    }
+
+    pub(super) fn next(&mut self, monitor: &mut Monitor) -> Option<Box<[Biclique]>> {
+        loop {
+            // The covers of the components may be incomplete.
+            if monitor.exhausted {
+                return None;
+            }
+
+            if let Some(Combination { split, indices }) = &mut self.current {
+                let cliques = self
+                    .components
+                    .iter()
+                    .zip(split.iter().zip(indices.iter()))
+                    .flat_map(|(c, (&s, &i))| c.covers(s)[i].iter().cloned())
+                    .collect();
+
+                // Advance to the next combination of covers.
+                let mut i = indices.len();
+                loop {
+                    if i == 0 {
+                        self.current = None;
+                        break;
+                    }
+
+                    i -= 1;
+                    indices[i] += 1;
+                    if indices[i] < self.components[i].covers(split[i]).len() {
+                        break;
+                    }
+                    indices[i] = 0;
+                }
+
+                return Some(cliques);
+            }
+
+            if let Some(split) = self.splits.pop() {
+                let indices = vec![0; split.len()].into_boxed_slice();
+                self.current = Some(Combination { split, indices });
+            } else if self.size <= self.max_size {
+                self.splits = self.splits_of(self.size, monitor);
+                self.splits.reverse();
+                self.size += 1;
+            } else {
+                return None;
+            }
+        }
+    }
+}
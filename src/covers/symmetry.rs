@@ -0,0 +1,230 @@
+//! Skipping parts of the search which are symmetric to explored ones.
+//!
+//! Once a layer has been explored, all covers extending it have been found. An
+//! automorphism maps these covers onto the covers extending the image of the
+//! layer, so a layer whose image contains an explored layer only leads to
+//! images of covers which have already been found. We check each layer against
+//! each explored layer in [`Containment`](super::containment::Containment), but
+//! only for the automorphisms which map a biclique of the layer onto the first
+//! maximal biclique of the explored one, or at least onto a superset of its
+//! first biclique if none of them are maximal.
+//!
+//! The explored layers then only account for the covers they lead to up to
+//! symmetry, which is why the covers found have to be expanded to their orbits.
+//! To skip the covers of orbits which have already been emitted, we remember
+//! their canonical forms, i.e. the smallest cover of each orbit.
+//!
+//! This replaces lex-leader constraints on the guessed edges: a branch which
+//! such a constraint would cut off is the image of a sibling explored before
+//! it, so its layer contains the image of an explored layer anyway. Combining
+//! both would be wrong, as they keep different members of an orbit.
+use crate::reduce::Kernel;
+use crate::symmetry::{group_elements, Automorphism};
+use crate::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// How [`BicliqueCovers`] deals with automorphisms of the bigraph, see [`Bigraph::automorphisms`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Searches all symmetric copies of each partial cover.
+    #[default]
+    Ignore,
+    /// Skips branches which are symmetric to explored ones and yields a single
+    /// cover of each orbit, i.e. of each set of covers which automorphisms map
+    /// onto each other.
+    Representatives,
+    /// Skips symmetric branches like [`Symmetry::Representatives`], but yields
+    /// every cover of each orbit. With [`BicliqueCovers::maximal_only`], this
+    /// yields the same covers as [`Symmetry::Ignore`]. Otherwise, which covers
+    /// are found depends on the order of the search, so this yields the orbits
+    /// of the covers found.
+    Orbits,
+}
+
+/// The most automorphisms we check layers against. Leaving some out only
+/// means that fewer symmetric layers get discarded, and checking against
+/// more of them quickly costs more than the layers skipped save.
+const MAX_CHECKED: usize = 1 << 8;
+
+/// The most automorphisms we list to find the canonical forms of covers.
+/// Covers of bigraphs with more of them are expanded to their orbits instead.
+const MAX_ELEMENTS: usize = 1 << 16;
+
+/// The automorphisms of the kernel being searched.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Symmetries {
+    generators: Vec<Automorphism>,
+    /// The elements of the group generated by `generators`, except for the
+    /// identity, in the order of [`group_elements`].
+    elements: Vec<Automorphism>,
+    /// Whether `elements` contains the whole group.
+    complete: bool,
+    /// The inverses of the first [`MAX_CHECKED`] elements.
+    inverses: Vec<Automorphism>,
+    /// For each biclique of an explored layer we compared against, the
+    /// bicliques mapped onto it together with the automorphisms doing so.
+    preimages: HashMap<Biclique, HashMap<Biclique, Vec<u32>>>,
+    /// For each biclique of a cover we looked for the canonical form of, its
+    /// images under `elements` together with the indices of the elements.
+    images: HashMap<Biclique, HashMap<Biclique, Vec<u32>>>,
+}
+
+pub(super) fn sorted(mut cliques: Vec<Biclique>) -> Box<[Biclique]> {
+    biclique_sort(&mut cliques);
+    cliques.into_boxed_slice()
+}
+
+/// Compares sorted covers by their bicliques.
+fn cover_cmp(a: &[Biclique], b: &[Biclique]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| biclique_cmp(a, b))
+        .find(|&ord| ord != Ordering::Equal)
+        .unwrap_or(a.len().cmp(&b.len()))
+}
+
+impl Symmetries {
+    pub(super) fn new(kernel: &Kernel) -> Symmetries {
+        let generators = kernel.automorphisms();
+        let elements = group_elements(&kernel.graph, &generators, MAX_ELEMENTS);
+        let inverses = elements
+            .iter()
+            .take(MAX_CHECKED)
+            .map(Automorphism::inverse)
+            .collect();
+        Symmetries {
+            complete: elements.len() < MAX_ELEMENTS,
+            generators,
+            elements,
+            inverses,
+            preimages: HashMap::new(),
+            images: HashMap::new(),
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.inverses.is_empty()
+    }
+
+    /// The indices of all automorphisms we check layers against.
+    pub(super) fn all(&self) -> Vec<u32> {
+        (0..self.inverses.len() as u32).collect()
+    }
+
+    /// The inverse of the automorphism with index `i`.
+    pub(super) fn inverse(&self, i: u32) -> &Automorphism {
+        &self.inverses[i as usize]
+    }
+
+    /// The indices of the automorphisms which map one of `cliques` onto `clique`.
+    pub(super) fn mapping_onto(&mut self, cliques: &[Biclique], clique: &Biclique) -> Vec<u32> {
+        let preimages = self.preimages(clique);
+        cliques
+            .iter()
+            .filter_map(|c| preimages.get(c))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// The indices of the automorphisms which map a subset of one of `cliques` onto `clique`.
+    pub(super) fn mapping_into(&mut self, cliques: &[Biclique], clique: &Biclique) -> Vec<u32> {
+        self.preimages(clique)
+            .iter()
+            .filter(|(preimage, _)| cliques.iter().any(|c| c.contains_clique(preimage)))
+            .flat_map(|(_, indices)| indices)
+            .copied()
+            .collect()
+    }
+
+    /// The bicliques which automorphisms map onto `clique`,
+    /// together with the indices of these automorphisms.
+    fn preimages(&mut self, clique: &Biclique) -> &HashMap<Biclique, Vec<u32>> {
+        let inverses = &self.inverses;
+        self.preimages.entry(clique.clone()).or_insert_with(|| {
+            let mut preimages: HashMap<Biclique, Vec<u32>> = HashMap::new();
+            for (i, inverse) in inverses.iter().enumerate() {
+                preimages
+                    .entry(inverse.biclique(clique))
+                    .or_default()
+                    .push(i as u32);
+            }
+            preimages
+        })
+    }
+
+    /// The images of `clique` under `elements`, together with their indices.
+    fn images(&mut self, clique: &Biclique) -> &HashMap<Biclique, Vec<u32>> {
+        let elements = &self.elements;
+        self.images.entry(clique.clone()).or_insert_with(|| {
+            let mut images: HashMap<Biclique, Vec<u32>> = HashMap::new();
+            for (i, a) in elements.iter().enumerate() {
+                images.entry(a.biclique(clique)).or_default().push(i as u32);
+            }
+            images
+        })
+    }
+
+    /// The smallest image of the sorted `cover` under the automorphisms,
+    /// which is the same for all covers of its orbit.
+    ///
+    /// Its first biclique is the smallest image of any of the bicliques of `cover`,
+    /// so we only have to look at the automorphisms mapping one of them onto it.
+    pub(super) fn canonical(&mut self, cover: &[Biclique]) -> Box<[Biclique]> {
+        if cover.is_empty() {
+            return cover.into();
+        }
+        if !self.complete {
+            let orbit = self.orbit(cover);
+            return orbit.into_iter().min_by(|a, b| cover_cmp(a, b)).unwrap();
+        }
+
+        let mut first: Option<Biclique> = None;
+        for c in cover {
+            for image in self.images(c).keys().chain([c]) {
+                if first
+                    .as_ref()
+                    .is_none_or(|first| biclique_cmp(image, first) == Ordering::Less)
+                {
+                    first = Some(image.clone());
+                }
+            }
+        }
+        let first = first.unwrap();
+
+        let mut canonical: Box<[Biclique]> = cover.into();
+        for c in cover {
+            let Some(indices) = self.images(c).get(&first).cloned() else {
+                continue;
+            };
+            for i in indices {
+                let a = &self.elements[i as usize];
+                let image = sorted(cover.iter().map(|c| a.biclique(c)).collect());
+                if cover_cmp(&image, &canonical) == Ordering::Less {
+                    canonical = image;
+                }
+            }
+        }
+        canonical
+    }
+
+    /// The covers automorphisms map `cliques` onto, starting with `cliques` itself,
+    /// with their bicliques sorted.
+    pub(super) fn orbit(&self, cliques: &[Biclique]) -> Vec<Box<[Biclique]>> {
+        let start = sorted(cliques.to_vec());
+        let mut seen = HashSet::from([start.clone()]);
+        let mut orbit = vec![start];
+        let mut i = 0;
+        while i < orbit.len() {
+            for a in &self.generators {
+                let image = sorted(orbit[i].iter().map(|c| a.biclique(c)).collect());
+                if seen.insert(image.clone()) {
+                    orbit.push(image);
+                }
+            }
+            i += 1;
+        }
+        orbit
+    }
+}
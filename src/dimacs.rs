@@ -0,0 +1,209 @@
+//! Encoding the search for a biclique cover as a SAT instance in the DIMACS CNF format,
+//! e.g. to compare against external SAT solvers.
+//!
+//! For a bigraph with `left` rows and `right` columns and a cover of size `k`,
+//! the variables are numbered as follows, where `i` is the index of a biclique:
+//!
+//! - `1 + i * (left + right) + x` is true if row `x` is part of biclique `i`.
+//! - `1 + i * (left + right) + left + y` is true if column `y` is part of biclique `i`.
+//! - `1 + k * (left + right) + j * k + i` is true if the `j`-th required edge,
+//!   as returned by [`Bigraph::entries`], is covered by biclique `i`.
+use crate::forced::FoolingSet;
+use crate::*;
+use std::io::{self, Write};
+
+struct Variables {
+    left: u32,
+    right: u32,
+    k: usize,
+}
+
+impl Variables {
+    fn new(g: &Bigraph, k: usize) -> Variables {
+        Variables {
+            left: g.left(),
+            right: g.right(),
+            k,
+        }
+    }
+
+    fn clique_offset(&self, i: usize) -> u64 {
+        1 + i as u64 * u64::from(self.left + self.right)
+    }
+
+    fn row(&self, x: u32, i: usize) -> u64 {
+        self.clique_offset(i) + u64::from(x)
+    }
+
+    fn column(&self, y: u32, i: usize) -> u64 {
+        self.clique_offset(i) + u64::from(self.left) + u64::from(y)
+    }
+
+    fn covered(&self, j: usize, i: usize) -> u64 {
+        self.clique_offset(self.k) + (j * self.k + i) as u64
+    }
+}
+
+/// A formula in conjunctive normal form, with literals in the DIMACS convention.
+pub(crate) struct Formula {
+    pub(crate) variables: u64,
+    pub(crate) clauses: Vec<Vec<i64>>,
+}
+
+/// The formula written by [`write_cnf`].
+pub(crate) fn encode(g: &Bigraph, k: usize, fooling_set: &FoolingSet) -> Formula {
+    assert!(fooling_set.verify(g), "not a fooling set of this bigraph");
+    let vars = Variables::new(g, k);
+    let as_literal = |v: u64| v as i64;
+
+    let mut clauses = Vec::new();
+    for (j, Edge(x, y)) in g.entries().enumerate() {
+        clauses.push((0..k).map(|i| as_literal(vars.covered(j, i))).collect());
+        for i in 0..k {
+            let covered = -as_literal(vars.covered(j, i));
+            clauses.push(vec![covered, as_literal(vars.row(x, i))]);
+            clauses.push(vec![covered, as_literal(vars.column(y, i))]);
+        }
+    }
+
+    for x in 0..g.left() {
+        for y in (0..g.right()).filter(|&y| !g.allows(Edge(x, y))) {
+            for i in 0..k {
+                let (row, column) = (vars.row(x, i), vars.column(y, i));
+                clauses.push(vec![-as_literal(row), -as_literal(column)]);
+            }
+        }
+    }
+
+    for (i, &Edge(x, y)) in fooling_set.edges().iter().take(k).enumerate() {
+        clauses.push(vec![as_literal(vars.row(x, i))]);
+        clauses.push(vec![as_literal(vars.column(y, i))]);
+    }
+
+    Formula {
+        variables: variable_count(g, k),
+        clauses,
+    }
+}
+
+/// The non-empty bicliques described by `model`, which contains
+/// the values of the variables of [`encode`], starting with `1`.
+pub(crate) fn cliques(g: &Bigraph, k: usize, model: &[bool]) -> Vec<Biclique> {
+    let vars = Variables::new(g, k);
+    let value = |v: u64| model[v as usize - 1];
+
+    (0..k)
+        .map(|i| Biclique {
+            left: (0..g.left()).filter(|&x| value(vars.row(x, i))).collect(),
+            right: (0..g.right())
+                .filter(|&y| value(vars.column(y, i)))
+                .collect(),
+        })
+        .filter(|c| !c.left.is_empty() && !c.right.is_empty())
+        .collect()
+}
+
+/// Writes a CNF formula to `w` which is satisfiable iff `g` has a biclique cover
+/// with at most `k` elements.
+///
+/// No two edges of `fooling_set` can be part of the same biclique, so the formula
+/// puts the `i`-th of them into the `i`-th biclique to break the symmetry between
+/// the bicliques. The fooling set is usually [`forced::fooling_set`], but a smaller
+/// one works as well.
+///
+/// # Panics
+///
+/// Panics if `fooling_set` is not a fooling set of `g`.
+pub fn write_cnf<W: Write>(
+    g: &Bigraph,
+    k: usize,
+    fooling_set: &FoolingSet,
+    mut w: W,
+) -> io::Result<()> {
+    let formula = encode(g, k, fooling_set);
+    writeln!(
+        w,
+        "c biclique cover with at most {} elements of a {} by {} bigraph",
+        k,
+        g.left(),
+        g.right()
+    )?;
+    writeln!(w, "p cnf {} {}", formula.variables, formula.clauses.len())?;
+    for clause in &formula.clauses {
+        for literal in clause {
+            write!(w, "{} ", literal)?;
+        }
+        writeln!(w, "0")?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The solver did not report a satisfying assignment.
+    NoSolution,
+    /// A token which is not a literal of the formula.
+    InvalidLiteral(String),
+    /// The assignment does not describe a biclique cover, i.e. it doesn't
+    /// satisfy the formula written by [`write_cnf`].
+    NotACover,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NoSolution => write!(f, "no satisfying assignment"),
+            DecodeError::InvalidLiteral(s) => write!(f, "invalid literal `{}`", s),
+            DecodeError::NotACover => write!(f, "the assignment is not a biclique cover"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Turns the output of a SAT solver for the formula written by [`write_cnf`]
+/// back into a biclique cover of `g`.
+///
+/// This accepts both the format of the SAT competition, with the assignment
+/// on lines starting with `v`, and plain lists of literals as written by
+/// MiniSat. Variables without a value are treated as false.
+pub fn decode(g: &Bigraph, k: usize, solution: &str) -> Result<BicliqueCover, DecodeError> {
+    let variables = variable_count(g, k);
+    let mut model = vec![false; variables as usize];
+    for line in solution.lines().map(str::trim) {
+        let literals = match line.split_whitespace().next() {
+            None | Some("c") | Some("SAT") | Some("SATISFIABLE") => continue,
+            Some("s") if line == "s SATISFIABLE" => continue,
+            Some("s") | Some("UNSAT") | Some("UNSATISFIABLE") | Some("INDET") => {
+                return Err(DecodeError::NoSolution)
+            }
+            Some("v") => &line[1..],
+            Some(_) => line,
+        };
+
+        for token in literals.split_whitespace() {
+            let invalid = || DecodeError::InvalidLiteral(token.to_string());
+            let literal: i64 = token.parse().map_err(|_| invalid())?;
+            if literal.unsigned_abs() > variables {
+                return Err(invalid());
+            } else if literal > 0 {
+                model[literal as usize - 1] = true;
+            }
+        }
+    }
+
+    let cover = BicliqueCover {
+        elements: cliques(g, k, &model).into_boxed_slice(),
+    };
+    if cover.consistent(g) {
+        Ok(BicliqueCover::new(g, cover.elements))
+    } else {
+        Err(DecodeError::NotACover)
+    }
+}
+
+/// The number of variables of the formula for a cover of `g` with `k` elements.
+fn variable_count(g: &Bigraph, k: usize) -> u64 {
+    Variables::new(g, k).covered(g.entries().count(), 0) - 1
+}
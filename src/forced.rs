@@ -1,15 +1,132 @@
 use std::collections::HashSet;
+use std::time::Instant;
 
 use crate::*;
 
+/// A set of edges no two of which can be part of the same biclique.
+///
+/// Its size is a lower bound for the size of every biclique cover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoolingSet {
+    edges: Vec<Edge>,
+    complete: bool,
+}
+
+impl FoolingSet {
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Whether the search for this fooling set ran until the end,
+    /// instead of stopping early because it ran out of budget.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Checks that this is actually a fooling set of `g`, i.e. that
+    /// it only contains edges of `g` and that no two of them may share
+    /// a biclique.
+    pub fn verify(&self, g: &Bigraph) -> bool {
+        for (i, &e) in self.edges.iter().enumerate() {
+            if e.0 >= g.left() || e.1 >= g.right() || !g.get(e) {
+                return false;
+            }
+
+            if self.edges[..i].iter().any(|&o| g.may_share(e, o)) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// How long to search for a large fooling set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Budget {
+    /// Search until the largest fooling set has been found.
+    #[default]
+    Exact,
+    /// Stop after visiting the given number of search nodes.
+    Nodes(u64),
+    /// Stop once the deadline has passed.
+    Deadline(Instant),
+}
+
+struct Limit {
+    budget: Budget,
+    nodes: u64,
+    exhausted: bool,
+}
+
+impl Limit {
+    fn new(budget: Budget) -> Limit {
+        Limit {
+            budget,
+            nodes: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Accounts for a new search node, returning `false` if
+    /// we're out of budget.
+    fn step(&mut self) -> bool {
+        self.nodes += 1;
+        self.exhausted |= match self.budget {
+            Budget::Exact => false,
+            Budget::Nodes(max) => self.nodes > max,
+            // Checking the time is comparatively expensive.
+            Budget::Deadline(deadline) => {
+                self.nodes.is_multiple_of(1024) && Instant::now() >= deadline
+            }
+        };
+        !self.exhausted
+    }
+}
+
+/// Computes a fooling set of `g`, searching until the largest one has been found.
+pub fn fooling_set(g: &Bigraph) -> FoolingSet {
+    fooling_set_with_budget(g, Budget::Exact)
+}
+
+/// Computes a fooling set of `g`, stopping early once `budget` is exhausted.
+///
+/// Stopping early still results in a valid, but potentially smaller, fooling set.
+pub fn fooling_set_with_budget(g: &Bigraph, budget: Budget) -> FoolingSet {
+    let mut limit = Limit::new(budget);
+    let edges = search(g, &mut limit);
+    FoolingSet {
+        edges,
+        complete: !limit.exhausted,
+    }
+}
+
 pub fn forced_elements(g: &Bigraph) -> Vec<Edge> {
+    fooling_set(g).edges
+}
+
+fn search(g: &Bigraph, limit: &mut Limit) -> Vec<Edge> {
     let mut mapping: Vec<_> = g.entries().collect();
 
-    let mut guaranteed = optimal_forced_elements(&mapping);
-    mapping.retain(|&e| guaranteed.iter().all(|&o| !g.may_share(e, o)));
+    // Both of these shortcuts rely on the fact that only
+    // edges may share a biclique, so we can't use them if
+    // the bigraph has optional entries.
+    let mut guaranteed = Vec::new();
+    if !g.has_optional() {
+        guaranteed = optimal_forced_elements(&mapping);
+        mapping.retain(|&e| guaranteed.iter().all(|&o| !g.may_share(e, o)));
 
-    let dominated_entries = dominated_entries(g, &mapping);
-    mapping.retain(|e| !dominated_entries.contains(e));
+        let dominated_entries = dominated_entries(g, &mapping);
+        mapping.retain(|e| !dominated_entries.contains(e));
+    }
 
     mapping.sort_by_cached_key(|&e| g.entries().filter(|&o| g.may_share(e, o)).count());
     let mut visibility = Vec::new();
@@ -30,7 +147,11 @@ pub fn forced_elements(g: &Bigraph) -> Vec<Edge> {
         };
 
         let possible = visibility[first].clone();
-        recur(cx, &mut vec![mapping[first]], &mut best, possible);
+        recur(cx, limit, &mut vec![mapping[first]], &mut best, possible);
+        if limit.exhausted {
+            break;
+        }
+
         best_possible_improvement.push(best.len());
     }
 
@@ -147,7 +268,21 @@ struct Cx<'x> {
     best_possible_improvement: &'x [usize],
 }
 
-fn recur(cx: Cx<'_>, chosen: &mut Vec<Edge>, best: &mut Vec<Edge>, mut possible: TBitSet<usize>) {
+fn recur(
+    cx: Cx<'_>,
+    limit: &mut Limit,
+    chosen: &mut Vec<Edge>,
+    best: &mut Vec<Edge>,
+    mut possible: TBitSet<usize>,
+) {
+    if !limit.step() {
+        // Out of budget, the current `chosen` is still a valid fooling set.
+        if chosen.len() > best.len() {
+            best.clone_from(chosen);
+        }
+        return;
+    }
+
     if best.len() >= chosen.len() + possible.element_count() {
         return;
     }
@@ -164,7 +299,7 @@ fn recur(cx: Cx<'_>, chosen: &mut Vec<Edge>, best: &mut Vec<Edge>, mut possible:
         let new_possible = possible.intersection(&cx.visibility[first]);
 
         let ignore_check_without = new_possible == possible;
-        recur(cx, chosen, best, new_possible);
+        recur(cx, limit, chosen, best, new_possible);
         chosen.pop();
 
         // We don't choose `first`.
@@ -176,8 +311,12 @@ fn recur(cx: Cx<'_>, chosen: &mut Vec<Edge>, best: &mut Vec<Edge>, mut possible:
             return;
         }
 
+        if limit.exhausted {
+            return;
+        }
+
         possible.remove(first);
-        recur(cx, chosen, best, possible);
+        recur(cx, limit, chosen, best, possible);
     } else if chosen.len() > best.len() {
         best.clone_from(chosen);
     }
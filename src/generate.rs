@@ -0,0 +1,102 @@
+//! Random and structured bigraphs, mostly useful for benchmarks and tests.
+use crate::*;
+use rand::{seq::SliceRandom, Rng};
+
+impl Bigraph {
+    /// A random bigraph where each entry is an edge with probability `p`.
+    pub fn random<R: Rng + ?Sized>(left: u32, right: u32, p: f64, rng: &mut R) -> Bigraph {
+        let mut g = Bigraph::new(left, right);
+        for x in 0..left {
+            for y in 0..right {
+                if rng.gen_bool(p) {
+                    g.add(Edge(x, y));
+                }
+            }
+        }
+        g
+    }
+}
+
+/// Chooses each of `0..len` with probability `p`, but at least one of them.
+fn random_subset<R: Rng + ?Sized>(len: u32, p: f64, rng: &mut R) -> TBitSet<u32> {
+    let mut set: TBitSet<u32> = (0..len).filter(|_| rng.gen_bool(p)).collect();
+    if set.is_empty() {
+        set.add(rng.gen_range(0..len));
+    }
+    set
+}
+
+/// The union of `k` random bicliques, each of them containing every row
+/// and column with probability `p`, together with these bicliques.
+///
+/// The boolean rank of the result is at most `k`.
+pub fn planted_cover<R: Rng + ?Sized>(
+    left: u32,
+    right: u32,
+    k: usize,
+    p: f64,
+    rng: &mut R,
+) -> (Bigraph, BicliqueCover) {
+    assert!(
+        left > 0 && right > 0,
+        "cannot plant bicliques in an empty bigraph"
+    );
+    let cliques: Box<[Biclique]> = (0..k)
+        .map(|_| Biclique {
+            left: random_subset(left, p, rng),
+            right: random_subset(right, p, rng),
+        })
+        .collect();
+
+    let mut g = Bigraph::new(left, right);
+    for c in cliques.iter() {
+        for x in c.left.iter() {
+            g.extend(c.right.iter().map(|y| Edge(x, y)));
+        }
+    }
+
+    let cover = BicliqueCover::new(&g, cliques);
+    (g, cover)
+}
+
+/// The complete `n` by `n` bigraph without the entries `(x, n - 1 - x)`,
+/// so each row and column is missing exactly one entry.
+pub fn crown(n: u32) -> Bigraph {
+    let mut g = Bigraph::new(n, n);
+    for x in 0..n {
+        g.set_row(x, (0..n).filter(|&y| y != n - 1 - x));
+    }
+    g
+}
+
+/// The complete bigraph with a single random entry missing in every row.
+///
+/// If `distinct` is set, the missing entries are in different columns,
+/// which requires `left <= right`.
+pub fn missing_per_row<R: Rng + ?Sized>(
+    left: u32,
+    right: u32,
+    distinct: bool,
+    rng: &mut R,
+) -> Bigraph {
+    assert!(right > 0 || left == 0, "no column to remove an entry from");
+    let mut columns: Vec<u32> = (0..right).collect();
+    if distinct {
+        assert!(
+            left <= right,
+            "not enough columns for distinct missing entries"
+        );
+        columns.shuffle(rng);
+    }
+
+    let mut g = Bigraph::new(left, right);
+    for x in 0..left {
+        let missing = if distinct {
+            columns[x as usize]
+        } else {
+            rng.gen_range(0..right)
+        };
+        g.set_row(x, (0..right).filter(|&y| y != missing));
+    }
+    g
+}
@@ -0,0 +1,220 @@
+//! Finding small biclique covers quickly, without any guarantees about their size.
+//!
+//! We greedily grow bicliques around edges which are difficult to cover and then
+//! improve the resulting cover by removing redundant bicliques and merging pairs
+//! of bicliques whose remaining edges fit into a single one.
+use crate::*;
+use std::mem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeuristicOptions {
+    /// How often to try improving the greedy cover, `0` disables local search.
+    pub rounds: usize,
+}
+
+impl Default for HeuristicOptions {
+    fn default() -> Self {
+        HeuristicOptions { rounds: 4 }
+    }
+}
+
+/// Computes a biclique cover of `g` consisting of maximal bicliques,
+/// which is usually small but not necessarily minimal.
+///
+/// This is a lot faster than [`minimum_biclique_cover`] and
+/// its size is an upper bound for the [`boolean_rank`].
+pub fn heuristic_cover(g: &Bigraph, options: HeuristicOptions) -> BicliqueCover {
+    let mut cliques = greedy(g);
+    improve(g, &mut cliques, options);
+    BicliqueCover::new(g, cliques.into_boxed_slice())
+}
+
+/// Tries to make a cover of `g` smaller using the local search of [`heuristic_cover`].
+pub(crate) fn improve(g: &Bigraph, cliques: &mut Vec<Biclique>, options: HeuristicOptions) {
+    for _ in 0..options.rounds {
+        remove_redundant(g, cliques);
+        merge_pairs(g, cliques);
+        reshape(g, cliques);
+    }
+
+    remove_redundant(g, cliques);
+}
+
+/// The required edges of `clique`.
+fn edges<'a>(g: &'a Bigraph, clique: &'a Biclique) -> impl Iterator<Item = Edge> + 'a {
+    clique
+        .left
+        .iter()
+        .flat_map(move |x| clique.right.iter().map(move |y| Edge(x, y)))
+        .filter(|&e| g.get(e))
+}
+
+/// The indices of all required edges which are not part of `cliques`.
+fn uncovered(g: &Bigraph, cliques: &[Biclique]) -> TBitSet<usize> {
+    g.entries()
+        .filter(|&e| !cliques.iter().any(|c| c.contains(e)))
+        .map(|e| g.edge_index(e))
+        .collect()
+}
+
+/// Extends `clique` to a maximal biclique.
+pub(crate) fn close(g: &Bigraph, clique: &mut Biclique) {
+    clique.right = g.common_right(&clique.left);
+    clique.left = g.common_left(&clique.right);
+}
+
+/// The uncovered edge which can be part of the fewest bicliques.
+fn hardest_edge(g: &Bigraph, uncovered: &TBitSet<usize>) -> Option<Edge> {
+    let degree = |e: Edge| {
+        let row = (0..g.right).filter(|&y| g.allows(Edge(e.0, y))).count();
+        let column = (0..g.left).filter(|&x| g.allows(Edge(x, e.1))).count();
+        row + column
+    };
+
+    uncovered
+        .iter()
+        .map(|i| g.edge_from_index(i))
+        .min_by_key(|&e| degree(e))
+}
+
+/// Grows a maximal biclique containing `e`, repeatedly adding the row or
+/// column which covers the most edges in `uncovered`.
+fn grow(g: &Bigraph, uncovered: &TBitSet<usize>, Edge(x, y): Edge) -> Biclique {
+    let mut clique = Biclique {
+        left: [x].into_iter().collect(),
+        right: [y].into_iter().collect(),
+    };
+
+    enum Extension {
+        Row(u32),
+        Column(u32),
+    }
+
+    loop {
+        let gain = |e: Edge| uncovered.get(g.edge_index(e));
+        let mut best = None;
+        let mut best_gain = 0;
+        for x in g.common_left(&clique.right).iter() {
+            let n = clique.right.iter().filter(|&y| gain(Edge(x, y))).count();
+            if !clique.left.get(x) && n > best_gain {
+                best = Some(Extension::Row(x));
+                best_gain = n;
+            }
+        }
+
+        for y in g.common_right(&clique.left).iter() {
+            let n = clique.left.iter().filter(|&x| gain(Edge(x, y))).count();
+            if !clique.right.get(y) && n > best_gain {
+                best = Some(Extension::Column(y));
+                best_gain = n;
+            }
+        }
+
+        match best {
+            Some(Extension::Row(x)) => clique.left.add(x),
+            Some(Extension::Column(y)) => clique.right.add(y),
+            None => break,
+        }
+    }
+
+    close(g, &mut clique);
+    clique
+}
+
+fn greedy(g: &Bigraph) -> Vec<Biclique> {
+    let mut cliques = Vec::new();
+    let mut uncovered = uncovered(g, &[]);
+    while let Some(e) = hardest_edge(g, &uncovered) {
+        let clique = grow(g, &uncovered, e);
+        for e in edges(g, &clique) {
+            uncovered.remove(g.edge_index(e));
+        }
+        cliques.push(clique);
+    }
+
+    cliques
+}
+
+/// Removes bicliques whose edges are all covered by other bicliques.
+fn remove_redundant(g: &Bigraph, cliques: &mut Vec<Biclique>) {
+    // Smaller bicliques are more likely to be redundant.
+    cliques.sort_by_key(|c| c.left.element_count() * c.right.element_count());
+    let mut i = 0;
+    while i < cliques.len() {
+        let redundant = edges(g, &cliques[i]).all(|e| {
+            cliques
+                .iter()
+                .enumerate()
+                .any(|(j, c)| j != i && c.contains(e))
+        });
+
+        if redundant {
+            cliques.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Replaces two bicliques with a single one if the edges which
+/// are only covered by them fit into a single biclique.
+fn merge_pairs(g: &Bigraph, cliques: &mut Vec<Biclique>) {
+    'restart: loop {
+        for i in 0..cliques.len() {
+            for j in i + 1..cliques.len() {
+                let is_covered = |e: Edge| {
+                    cliques
+                        .iter()
+                        .enumerate()
+                        .any(|(k, c)| k != i && k != j && c.contains(e))
+                };
+
+                let mut merged = Biclique::empty();
+                for e in edges(g, &cliques[i]).chain(edges(g, &cliques[j])) {
+                    if !is_covered(e) {
+                        merged.left.add(e.0);
+                        merged.right.add(e.1);
+                    }
+                }
+
+                // If there are no such edges, `remove_redundant` takes care of them.
+                if !merged.is_empty()
+                    && merged
+                        .left
+                        .iter()
+                        .all(|x| merged.right.iter().all(|y| g.allows(Edge(x, y))))
+                {
+                    close(g, &mut merged);
+                    cliques[i] = merged;
+                    cliques.swap_remove(j);
+                    continue 'restart;
+                }
+            }
+        }
+
+        break;
+    }
+}
+
+/// Regrows each biclique from the edges only it covers, which
+/// may allow other bicliques to be removed or merged later on.
+fn reshape(g: &Bigraph, cliques: &mut [Biclique]) {
+    for i in 0..cliques.len() {
+        let old = mem::replace(&mut cliques[i], Biclique::empty());
+        let uncovered = uncovered(g, cliques);
+        let Some(e) = hardest_edge(g, &uncovered) else {
+            cliques[i] = old;
+            continue;
+        };
+
+        let clique = grow(g, &uncovered, e);
+        cliques[i] = if uncovered
+            .iter()
+            .all(|index| clique.contains(g.edge_from_index(index)))
+        {
+            clique
+        } else {
+            old
+        };
+    }
+}
@@ -0,0 +1,215 @@
+//! Keeping a minimum biclique cover up to date while editing a bigraph.
+//!
+//! Changing a single entry changes the boolean rank by at most one: a new edge
+//! can always be covered by an additional biclique, and the bicliques containing
+//! a removed edge can give up its row, with one more biclique covering the rest
+//! of that row. The reverse edits show that the rank can't drop by more than one
+//! either. So after repairing the cover locally, it's often enough to find a
+//! fooling set of the right size, which we also repair after each edit.
+//! Otherwise, we search the components containing the edited entry again,
+//! starting with the repaired cover.
+use crate::heuristic::{close, improve};
+use crate::*;
+
+/// A bigraph together with a minimum biclique cover, which is repaired after
+/// each edit instead of being searched for from scratch.
+#[derive(Debug, Clone)]
+pub struct IncrementalCover {
+    graph: Bigraph,
+    cover: BicliqueCover,
+    /// A fooling set of `graph`, which proves that `cover` is
+    /// minimal without searching if it's large enough.
+    fooling_set: Vec<Edge>,
+    /// A lower bound for the boolean rank of `graph`.
+    lower: usize,
+    limits: SearchLimits,
+}
+
+impl IncrementalCover {
+    /// Computes a minimum cover of `g` to start with.
+    pub fn new(g: Bigraph) -> IncrementalCover {
+        IncrementalCover::with_limits(g, SearchLimits::default())
+    }
+
+    /// Like [`IncrementalCover::new`], but applies `limits` to each search,
+    /// including the one for the initial cover.
+    ///
+    /// If a search gets cut off, the cover may not be minimal,
+    /// see [`IncrementalCover::completion`].
+    pub fn with_limits(g: Bigraph, limits: SearchLimits) -> IncrementalCover {
+        let (cover, completion) = minimum_biclique_cover_with_limits(&g, limits);
+        let lower = match completion {
+            Completion::Complete => cover.cliques().len(),
+            Completion::CutOff { lower_bound, .. } => lower_bound,
+        };
+        let budget = match limits.deadline {
+            Some(deadline) => forced::Budget::Deadline(deadline),
+            None => forced::Budget::Exact,
+        };
+        let fooling_set = forced::fooling_set_with_budget(&g, budget).edges().to_vec();
+
+        IncrementalCover {
+            graph: g,
+            cover,
+            fooling_set,
+            lower,
+            limits,
+        }
+    }
+
+    pub fn graph(&self) -> &Bigraph {
+        &self.graph
+    }
+
+    /// The current cover, which is minimal unless a search got cut off.
+    pub fn cover(&self) -> &BicliqueCover {
+        &self.cover
+    }
+
+    /// Whether the current cover is known to be minimal.
+    pub fn completion(&self) -> Completion {
+        let size = self.cover.cliques().len();
+        if self.lower == size {
+            Completion::Complete
+        } else {
+            Completion::CutOff {
+                lower_bound: self.lower,
+                upper_bound: Some(size),
+            }
+        }
+    }
+
+    /// Adds the edge `e`, see [`Bigraph::add`].
+    pub fn add(&mut self, e: Edge) {
+        self.set_cell(e, Cell::Required);
+    }
+
+    /// Removes the edge `e`, see [`Bigraph::remove`].
+    pub fn remove(&mut self, e: Edge) {
+        self.set_cell(e, Cell::Forbidden);
+    }
+
+    /// Changes the entry `e` to `cell` and repairs the cover.
+    pub fn set_cell(&mut self, e: Edge, cell: Cell) {
+        if self.graph.cell(e) == cell {
+            return;
+        }
+
+        let was_minimal = self.completion().is_complete();
+        self.graph.set_cell(e, cell);
+        let mut cliques = self.repair_cover(e);
+        self.repair_fooling_set();
+        self.lower = self.lower.saturating_sub(1).max(self.fooling_set.len());
+        debug_assert!(self.lower <= cliques.len());
+
+        if cliques.len() > self.lower {
+            cliques = self.search(e, cliques, was_minimal);
+        }
+        self.cover = BicliqueCover::new(&self.graph, cliques.into_boxed_slice());
+    }
+
+    /// Turns the current cover into one of the edited bigraph,
+    /// with at most one more biclique.
+    fn repair_cover(&self, e @ Edge(x, y): Edge) -> Vec<Biclique> {
+        let g = &self.graph;
+        let mut cliques = self.cover.cliques().to_vec();
+        if !g.allows(e) {
+            let mut row = Biclique::empty();
+            row.left.add(x);
+            for clique in cliques.iter_mut().filter(|c| c.contains(e)) {
+                clique.left.remove(x);
+                row.right.extend(clique.right.iter().filter(|&y2| y2 != y));
+            }
+
+            if !row.right.is_empty() {
+                cliques.push(row);
+            }
+        }
+
+        cliques.retain(|c| !c.left.is_empty() && !c.right.is_empty());
+        for clique in &mut cliques {
+            close(g, clique);
+        }
+
+        if g.get(e) && !cliques.iter().any(|c| c.contains(e)) {
+            let mut clique = Biclique::empty();
+            clique.left.add(x);
+            clique.right.add(y);
+            close(g, &mut clique);
+            cliques.push(clique);
+        }
+
+        improve(g, &mut cliques, HeuristicOptions::default());
+        cliques
+    }
+
+    /// Drops the edges of the fooling set which became invalid,
+    /// then greedily adds new ones.
+    fn repair_fooling_set(&mut self) {
+        let g = &self.graph;
+        let mut fooling_set: Vec<Edge> = Vec::with_capacity(self.fooling_set.len() + 1);
+        for e in self.fooling_set.iter().copied().chain(g.entries()) {
+            if g.get(e) && fooling_set.iter().all(|&o| o != e && !g.may_share(e, o)) {
+                fooling_set.push(e);
+            }
+        }
+        self.fooling_set = fooling_set;
+    }
+
+    /// Searches the components containing the row or column of `e` for smaller covers,
+    /// updating the lower bound.
+    fn search(
+        &mut self,
+        Edge(x, y): Edge,
+        cliques: Vec<Biclique>,
+        was_minimal: bool,
+    ) -> Vec<Biclique> {
+        let g = &self.graph;
+        let total = cliques.len();
+        let mut untouched = Vec::new();
+        let mut repaired = Vec::new();
+        let mut touched_lower = 0;
+        for (component, map) in g.components() {
+            let rows: Vec<u32> = (0..component.left()).map(|i| map.left(i)).collect();
+            let cols: Vec<u32> = (0..component.right()).map(|i| map.right(i)).collect();
+            let (inside, outside): (Vec<Biclique>, Vec<Biclique>) = cliques
+                .iter()
+                .cloned()
+                .partition(|c| c.left.iter().all(|row| rows.binary_search(&row).is_ok()));
+            if rows.binary_search(&x).is_err() && cols.binary_search(&y).is_err() {
+                untouched.extend(inside);
+                continue;
+            }
+
+            // Restricted to the other components, the cover is at least as large as their rank.
+            let lower = self.lower.saturating_sub(outside.len());
+            let inside = inside
+                .iter()
+                .map(|c| Biclique {
+                    left: c
+                        .left
+                        .iter()
+                        .map(|x| rows.binary_search(&x).unwrap() as u32)
+                        .collect(),
+                    right: c
+                        .right
+                        .iter()
+                        .map(|y| cols.binary_search(&y).unwrap() as u32)
+                        .collect(),
+                })
+                .collect();
+            let (best, lower) = covers::shrink_cover(&component, inside, lower, self.limits);
+            touched_lower += lower;
+            repaired.extend(best.iter().map(|c| c.lift(&map)));
+        }
+
+        // The other components haven't changed, so their part of a minimum cover is still minimal.
+        if was_minimal {
+            self.lower = self.lower.max(untouched.len() + touched_lower);
+        }
+
+        untouched.extend(repaired);
+        debug_assert!(untouched.len() <= total);
+        untouched
+    }
+}
@@ -1,8 +1,33 @@
-use std::{fmt, ops::ControlFlow};
+use std::{error::Error, fmt, ops::ControlFlow};
 use tindex::TBitSet;
 
+mod count;
 mod covers;
+pub mod dimacs;
 pub mod forced;
+pub mod generate;
+mod heuristic;
+mod incremental;
+pub mod lp;
+mod maximal;
+mod reduce;
+mod sample;
+mod sat;
+mod symmetry;
+mod text;
+mod transform;
+
+pub use count::count_covers;
+pub use covers::{
+    BicliqueCovers, Completion, ParallelOptions, SearchLimits, SearchStats, Symmetry,
+};
+pub use heuristic::{heuristic_cover, HeuristicOptions};
+pub use incremental::IncrementalCover;
+pub use maximal::{maximal_bicliques, MaximalBicliques};
+pub use sample::{sample_minimum_covers, SampleError};
+pub use symmetry::Automorphism;
+pub use text::{ParseBigraphError, ParseErrorKind};
+pub use transform::IndexMap;
 
 #[cfg(test)]
 mod tests;
@@ -10,28 +35,51 @@ mod tests;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Edge(pub u32, pub u32);
 
-#[derive(Debug)]
+/// An edge which is not part of a bigraph with the given size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub edge: Edge,
+    pub left: u32,
+    pub right: u32,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Edge(x, y) = self.edge;
+        write!(
+            f,
+            "edge ({}, {}) is out of range for a bigraph with {} rows and {} columns",
+            x, y, self.left, self.right
+        )
+    }
+}
+
+impl Error for OutOfRange {}
+
+/// The state of a single entry of a [`Bigraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// An edge which has to be covered.
+    Required,
+    /// Not an edge, so it must not be part of any biclique.
+    Forbidden,
+    /// A "don't care" entry, which may be part of bicliques
+    /// but does not have to be covered.
+    ///
+    /// Bicliques are only extended with optional entries if this is
+    /// needed to cover required edges, so the covers found for bigraphs
+    /// with optional entries need not consist of maximal bicliques,
+    /// unless [`BicliqueCovers::maximal_only`] is used.
+    Optional,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bigraph {
     left: u32,
     edge_x_offset: u8,
     right: u32,
     entries: TBitSet<usize>,
-}
-
-impl fmt::Display for Bigraph {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for x in 0..self.left() {
-            for y in 0..self.right() {
-                if self.get(Edge(x, y)) {
-                    write!(f, "x")?;
-                } else {
-                    write!(f, "_")?;
-                }
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
+    optional: TBitSet<usize>,
 }
 
 impl Bigraph {
@@ -41,9 +89,23 @@ impl Bigraph {
             edge_x_offset: right.next_power_of_two().trailing_zeros() as u8,
             right,
             entries: TBitSet::new(),
+            optional: TBitSet::new(),
         }
     }
 
+    /// Creates a bigraph with the given size containing the edges of `edges`.
+    pub fn from_edges<I: IntoIterator<Item = Edge>>(
+        left: u32,
+        right: u32,
+        edges: I,
+    ) -> Result<Bigraph, OutOfRange> {
+        let mut g = Bigraph::new(left, right);
+        for e in edges {
+            g.try_add(e)?;
+        }
+        Ok(g)
+    }
+
     pub fn left(&self) -> u32 {
         self.left
     }
@@ -70,24 +132,125 @@ impl Bigraph {
         self.entries.get(self.edge_index(e))
     }
 
+    pub fn try_get(&self, e: Edge) -> Result<bool, OutOfRange> {
+        self.check(e)?;
+        Ok(self.get(e))
+    }
+
+    /// Adds the edge `e`.
+    ///
+    /// Edges outside of the bigraph would silently change other entries,
+    /// so this panics in debug builds. Use [`Bigraph::try_add`] to handle
+    /// such edges.
     pub fn add(&mut self, e: Edge) {
-        self.entries.add(self.edge_index(e))
+        self.set_cell(e, Cell::Required)
+    }
+
+    pub fn try_add(&mut self, e: Edge) -> Result<(), OutOfRange> {
+        self.try_set_cell(e, Cell::Required)
+    }
+
+    /// Removes the entry `e`, making it forbidden.
+    pub fn remove(&mut self, e: Edge) {
+        self.set_cell(e, Cell::Forbidden)
+    }
+
+    /// Removes `e` if it is an edge and adds it otherwise.
+    pub fn toggle(&mut self, e: Edge) {
+        if self.get(e) {
+            self.remove(e)
+        } else {
+            self.add(e)
+        }
+    }
+
+    pub fn clear_row(&mut self, x: u32) {
+        for y in 0..self.right {
+            self.remove(Edge(x, y));
+        }
+    }
+
+    pub fn clear_column(&mut self, y: u32) {
+        for x in 0..self.left {
+            self.remove(Edge(x, y));
+        }
+    }
+
+    /// Replaces the row `x` so that it only contains edges to the columns in `ys`.
+    pub fn set_row<I: IntoIterator<Item = u32>>(&mut self, x: u32, ys: I) {
+        self.clear_row(x);
+        for y in ys {
+            self.add(Edge(x, y));
+        }
+    }
+
+    fn check(&self, e: Edge) -> Result<(), OutOfRange> {
+        if e.0 < self.left && e.1 < self.right {
+            Ok(())
+        } else {
+            Err(OutOfRange {
+                edge: e,
+                left: self.left,
+                right: self.right,
+            })
+        }
+    }
+
+    pub fn cell(&self, e: Edge) -> Cell {
+        let index = self.edge_index(e);
+        if self.entries.get(index) {
+            Cell::Required
+        } else if self.optional.get(index) {
+            Cell::Optional
+        } else {
+            Cell::Forbidden
+        }
+    }
+
+    pub fn set_cell(&mut self, e: Edge, cell: Cell) {
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.check(e) {
+                panic!("{}", err);
+            }
+        }
+
+        let index = self.edge_index(e);
+        self.entries.set(index, cell == Cell::Required);
+        self.optional.set(index, cell == Cell::Optional);
+    }
+
+    pub fn try_set_cell(&mut self, e: Edge, cell: Cell) -> Result<(), OutOfRange> {
+        self.check(e)?;
+        self.set_cell(e, cell);
+        Ok(())
+    }
+
+    /// Whether `e` may be part of a biclique, i.e. whether
+    /// it's either a required or an optional entry.
+    #[inline(always)]
+    fn allows(&self, e: Edge) -> bool {
+        let index = self.edge_index(e);
+        self.entries.get(index) || self.optional.get(index)
+    }
+
+    fn has_optional(&self) -> bool {
+        !self.optional.is_empty()
     }
 
     #[inline(always)]
     fn may_share(&self, a: Edge, b: Edge) -> bool {
-        self.get(Edge(a.0, b.1)) && self.get(Edge(b.0, a.1))
+        self.allows(Edge(a.0, b.1)) && self.allows(Edge(b.0, a.1))
     }
 
     fn may_add(&self, clique: &Biclique, e: Edge) -> bool {
         for x in clique.left.iter() {
-            if !self.get(Edge(x, e.1)) {
+            if !self.allows(Edge(x, e.1)) {
                 return false;
             }
         }
 
         for y in clique.right.iter() {
-            if !self.get(Edge(e.0, y)) {
+            if !self.allows(Edge(e.0, y)) {
                 return false;
             }
         }
@@ -95,20 +258,30 @@ impl Bigraph {
         true
     }
 
+    /// The columns which may be added to a biclique containing the rows `left`.
+    fn common_right(&self, left: &TBitSet<u32>) -> TBitSet<u32> {
+        (0..self.right)
+            .filter(|&y| left.iter().all(|x| self.allows(Edge(x, y))))
+            .collect()
+    }
+
+    /// The rows which may be added to a biclique containing the columns `right`.
+    fn common_left(&self, right: &TBitSet<u32>) -> TBitSet<u32> {
+        (0..self.left)
+            .filter(|&x| right.iter().all(|y| self.allows(Edge(x, y))))
+            .collect()
+    }
+
     pub fn is_maximal(&self, clique: &Biclique) -> bool {
         for x in 0..self.left {
-            if !clique.left.get(x) {
-                if clique.right.iter().all(|y| self.get(Edge(x, y))) {
-                    return false;
-                }
+            if !clique.left.get(x) && clique.right.iter().all(|y| self.allows(Edge(x, y))) {
+                return false;
             }
         }
 
         for y in 0..self.right {
-            if !clique.right.get(y) {
-                if clique.left.iter().all(|x| self.get(Edge(x, y))) {
-                    return false;
-                }
+            if !clique.right.get(y) && clique.left.iter().all(|x| self.allows(Edge(x, y))) {
+                return false;
             }
         }
 
@@ -140,6 +313,38 @@ impl Bigraph {
     }
 }
 
+impl Extend<Edge> for Bigraph {
+    fn extend<I: IntoIterator<Item = Edge>>(&mut self, iter: I) {
+        for e in iter {
+            self.add(e);
+        }
+    }
+}
+
+/// Creates the smallest bigraph containing all the given edges.
+impl FromIterator<Edge> for Bigraph {
+    fn from_iter<I: IntoIterator<Item = Edge>>(iter: I) -> Bigraph {
+        let edges: Vec<Edge> = iter.into_iter().collect();
+        let left = edges.iter().map(|e| e.0 + 1).max().unwrap_or(0);
+        let right = edges.iter().map(|e| e.1 + 1).max().unwrap_or(0);
+        let mut g = Bigraph::new(left, right);
+        g.extend(edges);
+        g
+    }
+}
+
+impl<const L: usize, const R: usize> From<[[Cell; R]; L]> for Bigraph {
+    fn from(arr: [[Cell; R]; L]) -> Bigraph {
+        let mut g = Bigraph::new(L as u32, R as u32);
+        for (x, row) in arr.iter().enumerate() {
+            for (y, &cell) in row.iter().enumerate() {
+                g.set_cell(Edge(x as u32, y as u32), cell);
+            }
+        }
+        g
+    }
+}
+
 impl<const L: usize, const R: usize> From<[[bool; R]; L]> for Bigraph {
     fn from(arr: [[bool; R]; L]) -> Bigraph {
         let mut g = Bigraph::new(L as u32, R as u32);
@@ -160,7 +365,8 @@ pub struct Biclique {
     pub right: TBitSet<u32>,
 }
 
-fn biclique_sort(bicliques: &mut [Biclique]) {
+/// The order used for the bicliques of a canonical [`BicliqueCover`].
+fn biclique_cmp(a: &Biclique, b: &Biclique) -> std::cmp::Ordering {
     use std::cmp::Ordering;
     let bitset_ord = |a: &TBitSet<u32>, b: &TBitSet<u32>| {
         let mut a_iter = a.iter().rev();
@@ -176,11 +382,13 @@ fn biclique_sort(bicliques: &mut [Biclique]) {
         }
     };
 
-    bicliques.sort_by(|a, b| {
-        bitset_ord(&a.left, &b.left)
-            .then_with(|| bitset_ord(&a.right, &b.right))
-            .reverse()
-    });
+    bitset_ord(&a.left, &b.left)
+        .then_with(|| bitset_ord(&a.right, &b.right))
+        .reverse()
+}
+
+fn biclique_sort(bicliques: &mut [Biclique]) {
+    bicliques.sort_by(biclique_cmp);
 }
 
 impl Biclique {
@@ -224,26 +432,48 @@ pub struct BicliqueCover {
 impl BicliqueCover {
     fn new(g: &Bigraph, elements: Box<[Biclique]>) -> Self {
         let mut this = BicliqueCover { elements };
-        this.consistent(g);
+        debug_assert!(this.consistent(g));
         this.canonicalize();
         this
     }
 
-    fn consistent(&self, g: &Bigraph) {
-        cfg_if::cfg_if! {
-            if #[cfg(debug_assertions)] {
-                for x in 0..g.left {
-                    for y in 0..g.right {
-                        assert_eq!(
-                            g.get(Edge(x, y)),
-                            self.elements.iter().any(|c| c.contains(Edge(x, y)))
-                        );
-                    }
+    fn new_partition(g: &Bigraph, elements: Box<[Biclique]>) -> Self {
+        let mut this = BicliqueCover { elements };
+        debug_assert!(this.consistent_partition(g));
+        this.canonicalize();
+        this
+    }
+
+    /// Checks that every required edge of `g` and none of its forbidden ones are covered.
+    fn consistent(&self, g: &Bigraph) -> bool {
+        (0..g.left).all(|x| {
+            (0..g.right).all(|y| {
+                let covered = self.elements.iter().any(|c| c.contains(Edge(x, y)));
+                match g.cell(Edge(x, y)) {
+                    Cell::Required => covered,
+                    Cell::Forbidden => !covered,
+                    Cell::Optional => true,
                 }
-            } else {
-                let _ = g;
-            }
-        }
+            })
+        })
+    }
+
+    /// Like [`consistent`](Self::consistent), but required edges must be covered exactly once.
+    fn consistent_partition(&self, g: &Bigraph) -> bool {
+        (0..g.left).all(|x| {
+            (0..g.right).all(|y| {
+                let count = self
+                    .elements
+                    .iter()
+                    .filter(|c| c.contains(Edge(x, y)))
+                    .count();
+                match g.cell(Edge(x, y)) {
+                    Cell::Required => count == 1,
+                    Cell::Forbidden => count == 0,
+                    Cell::Optional => true,
+                }
+            })
+        })
     }
 
     fn canonicalize(&mut self) {
@@ -283,12 +513,138 @@ impl BicliqueCover {
     }
 }
 
+/// Calls `f` with the biclique covers of `g` with at most `max_size` elements,
+/// starting with the smallest ones.
+///
+/// Identical rows and columns always end up in the same bicliques, so covers
+/// which split them up aren't passed to `f`, see [`BicliqueCovers::new`].
 pub fn biclique_covers<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
     g: &Bigraph,
     max_size: usize,
+    mut f: F,
+) -> ControlFlow<T> {
+    for cover in BicliqueCovers::new(g, max_size) {
+        f(cover)?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Like [`biclique_covers`], but spreads the search over multiple threads.
+///
+/// Passes the same covers to `f` as [`biclique_covers`], but only once the
+/// search is done, as it keeps all of them in memory. Unless
+/// `options.deterministic` is set, they're ordered by size but not necessarily
+/// in the same way as the sequential search.
+pub fn par_biclique_covers<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    options: ParallelOptions,
+    f: F,
+) -> ControlFlow<T> {
+    covers::par_covers(g, max_size, options, f)
+}
+
+/// Like [`par_biclique_covers`], but only passes covers consisting of distinct
+/// maximal bicliques to `f`, see [`BicliqueCovers::maximal_only`].
+///
+/// Every cover is only passed to `f` once, so unlike the sequential search this keeps
+/// all of them in memory. Unless `options.deterministic` is set,
+/// covers are passed to `f` as soon as any thread finds them, so they're not
+/// necessarily ordered by size and their order changes between runs.
+/// Unlike [`BicliqueCovers`], this doesn't search connected components separately.
+pub fn par_maximal_covers<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    options: ParallelOptions,
     f: F,
 ) -> ControlFlow<T> {
-    let forced_elements: Vec<Edge> = forced::forced_elements(g);
+    covers::par_maximal_covers(g, max_size, options, f)
+}
+
+/// Like [`biclique_covers`], but stops early once one of the `limits` is reached.
+pub fn biclique_covers_with_limits<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    limits: SearchLimits,
+    mut f: F,
+) -> ControlFlow<T, Completion> {
+    let mut covers = BicliqueCovers::with_limits(g, max_size, limits);
+    for cover in covers.by_ref() {
+        f(cover)?;
+    }
+
+    ControlFlow::Continue(covers.completion())
+}
+
+/// Calls `f` with all biclique partitions of `g` with at most `max_size` elements,
+/// starting with the smallest ones.
+///
+/// Unlike in a cover, every edge of a partition is part of exactly one biclique.
+/// Every row and column of a biclique in a partition is part of one of its
+/// required edges, optional entries are only included where necessary.
+pub fn biclique_partitions<T, F: FnMut(BicliqueCover) -> ControlFlow<T>>(
+    g: &Bigraph,
+    max_size: usize,
+    mut f: F,
+) -> ControlFlow<T> {
+    for partition in BicliqueCovers::partitions(g, max_size) {
+        f(partition)?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Computes a biclique cover of `g` with the smallest possible number of bicliques.
+///
+/// Each connected component is covered separately. The search starts with the
+/// cover found by [`heuristic_cover`] and looks for smaller ones until it either
+/// fails or reaches the size of the fooling set computed by [`forced::forced_elements`].
+pub fn minimum_biclique_cover(g: &Bigraph) -> BicliqueCover {
+    minimum_biclique_cover_with_limits(g, SearchLimits::default()).0
+}
+
+/// Like [`minimum_biclique_cover`], but stops early once one of the `limits` is reached.
+///
+/// In that case the returned cover is the smallest one found until then
+/// and may not be minimal.
+pub fn minimum_biclique_cover_with_limits(
+    g: &Bigraph,
+    limits: SearchLimits,
+) -> (BicliqueCover, Completion) {
+    let (cliques, completion) = covers::minimum_cover(g, limits);
+    (BicliqueCover::new(g, cliques), completion)
+}
+
+/// The engine used to find minimum biclique covers.
+///
+/// Which one is faster depends on the bigraph, the SAT solver tends to
+/// be better at proving that there is no smaller cover for dense ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Solver {
+    /// The search used by [`BicliqueCovers`].
+    #[default]
+    Search,
+    /// A built-in CDCL SAT solver, using the formula written by [`dimacs::write_cnf`].
+    Sat,
+}
+
+/// Like [`minimum_biclique_cover`], but using the given `solver`.
+pub fn minimum_biclique_cover_with_solver(g: &Bigraph, solver: Solver) -> BicliqueCover {
+    match solver {
+        Solver::Search => minimum_biclique_cover(g),
+        Solver::Sat => BicliqueCover::new(g, sat::minimum_cover(g)),
+    }
+}
+
+/// The boolean rank of `g`, i.e. the size of its minimum biclique cover.
+pub fn boolean_rank(g: &Bigraph) -> usize {
+    minimum_biclique_cover(g).cliques().len()
+}
 
-    covers::iterate(g, max_size, forced_elements, f)
+/// Like [`boolean_rank`], but using the given `solver`.
+pub fn boolean_rank_with_solver(g: &Bigraph, solver: Solver) -> usize {
+    minimum_biclique_cover_with_solver(g, solver)
+        .cliques()
+        .len()
 }
@@ -0,0 +1,265 @@
+//! Encoding the search for a biclique cover as an integer linear program
+//! in the CPLEX LP format, e.g. for MIP solvers.
+//!
+//! All variables are binary, where `i` is the index of a biclique:
+//!
+//! - `r_i_x` is set if row `x` is part of biclique `i`.
+//! - `c_i_y` is set if column `y` is part of biclique `i`.
+//! - `t_i_x_y` is set if the required edge `(x, y)` is covered by biclique `i`.
+//! - `u_i` is set if biclique `i` is used, only for [`Objective::Minimum`].
+use crate::forced::FoolingSet;
+use crate::*;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// What the program written by [`write_lp`] asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Any biclique cover with at most the given number of bicliques.
+    AtMost(usize),
+    /// A biclique cover with as few bicliques as possible.
+    ///
+    /// The number of bicliques is bounded by the size of the [`heuristic_cover`].
+    Minimum,
+}
+
+/// Writes `terms` as a sum, breaking lines every now and then
+/// as some solvers limit their length.
+fn write_sum<W: Write>(w: &mut W, terms: impl IntoIterator<Item = String>) -> io::Result<()> {
+    for (i, term) in terms.into_iter().enumerate() {
+        if i == 0 {
+            write!(w, " {}", term)?;
+        } else if i % 8 == 0 {
+            write!(w, "\n   + {}", term)?;
+        } else {
+            write!(w, " + {}", term)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes an integer linear program to `w` whose solutions are the biclique covers
+/// of `g` described by `objective`.
+///
+/// Just like [`dimacs::write_cnf`], the `i`-th edge of `fooling_set` is put
+/// into the `i`-th biclique to break the symmetry between the bicliques.
+/// For [`Objective::Minimum`], unused bicliques come last.
+///
+/// # Panics
+///
+/// Panics if `fooling_set` is not a fooling set of `g`.
+pub fn write_lp<W: Write>(
+    g: &Bigraph,
+    objective: Objective,
+    fooling_set: &FoolingSet,
+    mut w: W,
+) -> io::Result<()> {
+    assert!(fooling_set.verify(g), "not a fooling set of this bigraph");
+    let k = match objective {
+        Objective::AtMost(k) => k,
+        Objective::Minimum => heuristic_cover(g, HeuristicOptions::default())
+            .cliques()
+            .len(),
+    };
+    let minimum = objective == Objective::Minimum;
+
+    writeln!(
+        w,
+        "\\ biclique cover with at most {} elements of a {} by {} bigraph",
+        k,
+        g.left(),
+        g.right()
+    )?;
+    writeln!(w, "Minimize")?;
+    write!(w, " obj:")?;
+    if minimum {
+        write_sum(&mut w, (0..k).map(|i| format!("u_{}", i)))?;
+    } else {
+        // Any cover will do, but solvers reject an objective without terms.
+        write!(w, " 0 r_0_0")?;
+    }
+    writeln!(w)?;
+
+    writeln!(w, "Subject To")?;
+    for Edge(x, y) in g.entries() {
+        write!(w, " cover_{}_{}:", x, y)?;
+        write_sum(&mut w, (0..k).map(|i| format!("t_{}_{}_{}", i, x, y)))?;
+        writeln!(w, " >= 1")?;
+        for i in 0..k {
+            writeln!(
+                w,
+                " row_{}_{}_{}: t_{}_{}_{} - r_{}_{} <= 0",
+                i, x, y, i, x, y, i, x
+            )?;
+            writeln!(
+                w,
+                " column_{}_{}_{}: t_{}_{}_{} - c_{}_{} <= 0",
+                i, x, y, i, x, y, i, y
+            )?;
+        }
+    }
+
+    for x in 0..g.left() {
+        for y in (0..g.right()).filter(|&y| !g.allows(Edge(x, y))) {
+            for i in 0..k {
+                writeln!(
+                    w,
+                    " forbidden_{}_{}_{}: r_{}_{} + c_{}_{} <= 1",
+                    i, x, y, i, x, i, y
+                )?;
+            }
+        }
+    }
+
+    let pinned = fooling_set.len().min(k);
+    for (i, &Edge(x, y)) in fooling_set.edges().iter().take(pinned).enumerate() {
+        writeln!(w, " pin_row_{}: r_{}_{} = 1", i, i, x)?;
+        writeln!(w, " pin_column_{}: c_{}_{} = 1", i, i, y)?;
+    }
+
+    if minimum {
+        for i in 0..k {
+            for x in 0..g.left() {
+                writeln!(w, " used_{}_{}: r_{}_{} - u_{} <= 0", i, x, i, x, i)?;
+            }
+        }
+
+        for i in pinned..k.saturating_sub(1) {
+            writeln!(w, " order_{}: u_{} - u_{} >= 0", i, i, i + 1)?;
+        }
+    }
+
+    writeln!(w, "Binary")?;
+    for i in 0..k {
+        let rows = (0..g.left()).map(|x| format!("r_{}_{}", i, x));
+        let columns = (0..g.right()).map(|y| format!("c_{}_{}", i, y));
+        let edges = g.entries().map(|Edge(x, y)| format!("t_{}_{}_{}", i, x, y));
+        let used = minimum.then(|| format!("u_{}", i));
+        for name in rows.chain(columns).chain(edges).chain(used) {
+            writeln!(w, " {}", name)?;
+        }
+    }
+    writeln!(w, "End")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSolutionError {
+    /// The value of a variable is not a number.
+    InvalidValue(String),
+    /// A row or column which is not part of the bigraph.
+    OutOfRange(String),
+    /// The solution does not describe a biclique cover, e.g. because the solver
+    /// did not find one.
+    NotACover,
+}
+
+impl fmt::Display for ParseSolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSolutionError::InvalidValue(s) => write!(f, "invalid value `{}`", s),
+            ParseSolutionError::OutOfRange(s) => {
+                write!(f, "variable `{}` is out of range for the bigraph", s)
+            }
+            ParseSolutionError::NotACover => write!(f, "the solution is not a biclique cover"),
+        }
+    }
+}
+
+impl Error for ParseSolutionError {}
+
+/// A row or column variable of biclique `i`.
+enum Variable {
+    Row(usize, u32),
+    Column(usize, u32),
+}
+
+impl Variable {
+    fn parse(name: &str) -> Option<Variable> {
+        let mut parts = name.split('_');
+        let kind = parts.next()?;
+        let i = parts.next()?.parse().ok()?;
+        let index = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        match kind {
+            "r" => Some(Variable::Row(i, index)),
+            "c" => Some(Variable::Column(i, index)),
+            _ => None,
+        }
+    }
+}
+
+/// The value of the attribute `name` of an XML element on a single line.
+fn attribute<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let len = line[start..].find('"')?;
+    Some(&line[start..start + len])
+}
+
+/// Reconstructs the biclique cover of `g` from the solution of a MIP solver
+/// for the program written by [`write_lp`].
+///
+/// This accepts the solution files of CPLEX, which are XML, and the common
+/// formats of other solvers with one variable per line, followed by its value.
+/// Variables which don't appear are treated as zero.
+pub fn parse_solution(g: &Bigraph, solution: &str) -> Result<BicliqueCover, ParseSolutionError> {
+    let mut cliques: BTreeMap<usize, Biclique> = BTreeMap::new();
+    for line in solution.lines() {
+        let (variable, name, value) = if line.trim_start().starts_with('<') {
+            let Some(name) = attribute(line, "name") else {
+                continue;
+            };
+            let Some(variable) = Variable::parse(name) else {
+                continue;
+            };
+            let value = attribute(line, "value").unwrap_or("0");
+            (variable, name, value)
+        } else {
+            let mut tokens = line.split_whitespace();
+            let Some((variable, name)) = tokens
+                .by_ref()
+                .find_map(|name| Some((Variable::parse(name)?, name)))
+            else {
+                continue;
+            };
+            let value = tokens.next().unwrap_or("0");
+            (variable, name, value)
+        };
+
+        let value: f64 = value
+            .parse()
+            .map_err(|_| ParseSolutionError::InvalidValue(value.to_string()))?;
+        if value < 0.5 {
+            continue;
+        }
+
+        let (i, in_range) = match variable {
+            Variable::Row(i, x) => (i, x < g.left()),
+            Variable::Column(i, y) => (i, y < g.right()),
+        };
+        if !in_range {
+            return Err(ParseSolutionError::OutOfRange(name.to_string()));
+        }
+
+        let clique = cliques.entry(i).or_insert_with(Biclique::empty);
+        match variable {
+            Variable::Row(_, x) => clique.left.add(x),
+            Variable::Column(_, y) => clique.right.add(y),
+        }
+    }
+
+    let cover = BicliqueCover {
+        elements: cliques
+            .into_values()
+            .filter(|c| !c.left.is_empty() && !c.right.is_empty())
+            .collect(),
+    };
+    if cover.consistent(g) {
+        Ok(BicliqueCover::new(g, cover.elements))
+    } else {
+        Err(ParseSolutionError::NotACover)
+    }
+}
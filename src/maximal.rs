@@ -0,0 +1,124 @@
+//! Enumerating all maximal bicliques of a bigraph.
+//!
+//! This uses the MBEA algorithm from "On finding bicliques in bipartite graphs:
+//! a novel algorithm and its application to the integration of diverse biological
+//! data types" by Zhang et al., which only spends polynomial time per biclique and
+//! never emits the same biclique twice.
+use crate::*;
+
+/// Returns an iterator over all maximal bicliques of `g`.
+///
+/// Optional entries are treated like edges, so the returned
+/// bicliques are the ones accepted by [`Bigraph::is_maximal`].
+pub fn maximal_bicliques(g: &Bigraph) -> MaximalBicliques<'_> {
+    let mut candidates: Vec<u32> = (0..g.right)
+        .filter(|&y| (0..g.left).any(|x| g.allows(Edge(x, y))))
+        .collect();
+    // We take candidates from the back, so start with the columns
+    // with the most entries, which tends to keep the search shallow.
+    candidates.sort_by_key(|&y| (0..g.left).filter(|&x| g.allows(Edge(x, y))).count());
+
+    MaximalBicliques {
+        g,
+        stack: vec![Frame {
+            left: (0..g.left).collect(),
+            right: TBitSet::new(),
+            candidates,
+            excluded: Vec::new(),
+        }],
+    }
+}
+
+struct Frame {
+    left: TBitSet<u32>,
+    right: TBitSet<u32>,
+    /// Columns which may still be added to `right`.
+    candidates: Vec<u32>,
+    /// Columns which have already been tried, every biclique which
+    /// could be extended by one of these has already been emitted.
+    excluded: Vec<u32>,
+}
+
+/// An iterator over the maximal bicliques of a graph, see [`maximal_bicliques`].
+pub struct MaximalBicliques<'g> {
+    g: &'g Bigraph,
+    stack: Vec<Frame>,
+}
+
+impl MaximalBicliques<'_> {
+    /// The number of rows of `left` which are connected to `y`.
+    fn neighbours(&self, left: &TBitSet<u32>, y: u32) -> usize {
+        left.iter().filter(|&x| self.g.allows(Edge(x, y))).count()
+    }
+}
+
+impl Iterator for MaximalBicliques<'_> {
+    type Item = Biclique;
+
+    fn next(&mut self) -> Option<Biclique> {
+        while let Some(frame) = self.stack.last_mut() {
+            let Some(y) = frame.candidates.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            frame.excluded.push(y);
+
+            let g = self.g;
+            let left: TBitSet<u32> = frame
+                .left
+                .iter()
+                .filter(|&x| g.allows(Edge(x, y)))
+                .collect();
+            let size = left.element_count();
+            if size == 0 {
+                continue;
+            }
+
+            let frame = self.stack.last().unwrap();
+            let mut excluded = Vec::new();
+            let mut is_maximal = true;
+            for &v in &frame.excluded[..frame.excluded.len() - 1] {
+                match self.neighbours(&left, v) {
+                    0 => {}
+                    n if n == size => {
+                        is_maximal = false;
+                        break;
+                    }
+                    _ => excluded.push(v),
+                }
+            }
+
+            if !is_maximal {
+                continue;
+            }
+
+            let mut right = frame.right.clone();
+            right.add(y);
+            let mut candidates = Vec::new();
+            for &v in &frame.candidates {
+                match self.neighbours(&left, v) {
+                    0 => {}
+                    n if n == size => right.add(v),
+                    _ => candidates.push(v),
+                }
+            }
+
+            let biclique = Biclique {
+                left: left.clone(),
+                right: right.clone(),
+            };
+            if !candidates.is_empty() {
+                self.stack.push(Frame {
+                    left,
+                    right,
+                    candidates,
+                    excluded,
+                });
+            }
+
+            return Some(biclique);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,145 @@
+use crate::*;
+
+/// A smaller bigraph which has the same maximal biclique covers as the original one.
+///
+/// Identical rows and columns are always part of the same maximal bicliques,
+/// so we merge them. Rows and columns without any entries are never part
+/// of a biclique, so they get dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct Kernel {
+    pub(crate) graph: Bigraph,
+    /// For each row of `graph`, the rows of the original bigraph it represents.
+    left: Box<[Box<[u32]>]>,
+    /// For each column of `graph`, the columns of the original bigraph it represents.
+    right: Box<[Box<[u32]>]>,
+}
+
+/// Groups `0..len` by the result of `line`, dropping all
+/// indices for which `line` only returns forbidden entries.
+fn groups(len: u32, merge_twins: bool, line: impl Fn(u32) -> Vec<Cell>) -> Vec<Vec<u32>> {
+    let mut groups: Vec<(Vec<Cell>, Vec<u32>)> = Vec::new();
+    for i in 0..len {
+        let cells = line(i);
+        if cells.iter().all(|&c| c == Cell::Forbidden) {
+            continue;
+        }
+
+        match groups.iter_mut().find(|(c, _)| merge_twins && *c == cells) {
+            Some((_, group)) => group.push(i),
+            None => groups.push((cells, vec![i])),
+        }
+    }
+
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+impl Bigraph {
+    /// The connected components of `self`, ordered by their first row, together
+    /// with maps back to `self`.
+    ///
+    /// Optional entries connect their row and column just like edges do.
+    /// Rows and columns without any entries are not part of any component.
+    pub fn components(&self) -> Vec<(Bigraph, IndexMap)> {
+        fn root(parent: &mut [u32], mut i: u32) -> u32 {
+            while parent[i as usize] != i {
+                parent[i as usize] = parent[parent[i as usize] as usize];
+                i = parent[i as usize];
+            }
+            i
+        }
+
+        // Rows are `0..left` and columns `left..left + right`.
+        let mut parent: Vec<u32> = (0..self.left + self.right).collect();
+        let mut used = vec![false; parent.len()];
+        for x in 0..self.left {
+            for y in 0..self.right {
+                if self.allows(Edge(x, y)) {
+                    let (a, b) = (root(&mut parent, x), root(&mut parent, self.left + y));
+                    parent[a as usize] = b;
+                    used[x as usize] = true;
+                    used[(self.left + y) as usize] = true;
+                }
+            }
+        }
+
+        let mut components: Vec<(u32, Vec<u32>, Vec<u32>)> = Vec::new();
+        for i in (0..parent.len() as u32).filter(|&i| used[i as usize]) {
+            let r = root(&mut parent, i);
+            let index = match components.iter().position(|&(c, _, _)| c == r) {
+                Some(index) => index,
+                None => {
+                    components.push((r, Vec::new(), Vec::new()));
+                    components.len() - 1
+                }
+            };
+
+            if i < self.left {
+                components[index].1.push(i);
+            } else {
+                components[index].2.push(i - self.left);
+            }
+        }
+
+        components
+            .into_iter()
+            .map(|(_, rows, cols)| self.induced(&rows, &cols))
+            .collect()
+    }
+}
+
+impl Kernel {
+    /// Computes the kernel of `g`.
+    ///
+    /// Merging identical rows and columns removes covers which use different
+    /// bicliques for them, which is only fine if we only care about maximal covers.
+    /// Use `merge_twins: false` to only drop empty rows and columns.
+    pub(crate) fn new(g: &Bigraph, merge_twins: bool) -> Kernel {
+        let left = groups(g.left, merge_twins, |x| {
+            (0..g.right).map(|y| g.cell(Edge(x, y))).collect()
+        });
+        let right = groups(g.right, merge_twins, |y| {
+            (0..g.left).map(|x| g.cell(Edge(x, y))).collect()
+        });
+
+        let rows: Vec<u32> = left.iter().map(|group| group[0]).collect();
+        let cols: Vec<u32> = right.iter().map(|group| group[0]).collect();
+        let (graph, _) = g.induced(&rows, &cols);
+        Kernel {
+            graph,
+            left: left.into_iter().map(Vec::into_boxed_slice).collect(),
+            right: right.into_iter().map(Vec::into_boxed_slice).collect(),
+        }
+    }
+
+    /// Maps a biclique of the kernel to the corresponding biclique of the original bigraph.
+    pub(crate) fn lift(&self, clique: &Biclique) -> Biclique {
+        Biclique {
+            left: clique
+                .left
+                .iter()
+                .flat_map(|x| self.left[x as usize].iter().copied())
+                .collect(),
+            right: clique
+                .right
+                .iter()
+                .flat_map(|y| self.right[y as usize].iter().copied())
+                .collect(),
+        }
+    }
+
+    /// The automorphisms of the kernel which come from automorphisms of the original
+    /// bigraph, i.e. which only map rows and columns onto ones representing as many.
+    pub(crate) fn automorphisms(&self) -> Vec<Automorphism> {
+        let sizes: Vec<u32> = self
+            .left
+            .iter()
+            .chain(self.right.iter())
+            .map(|group| group.len() as u32)
+            .collect();
+        self.graph.automorphisms_preserving(&sizes)
+    }
+
+    pub(crate) fn lift_all(&self, cliques: &[Biclique]) -> Box<[Biclique]> {
+        cliques.iter().map(|c| self.lift(c)).collect()
+    }
+}
@@ -0,0 +1,65 @@
+//! Sampling minimum biclique covers uniformly at random.
+//!
+//! The minimum covers of a bigraph are exactly the unions of minimum covers of
+//! its components, so picking one for each component independently is uniform
+//! as well. For each component, we count its minimum covers and pick random
+//! positions among them. Then we descend into the branches of the counting
+//! search which contain these positions, counting the covers of each branch
+//! on the way, so the covers never have to be enumerated.
+use crate::count::MinimumCovers;
+use crate::*;
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SampleError {
+    /// A component has more minimum covers than fit into a `u128`,
+    /// so they can't be picked uniformly.
+    TooManyCovers,
+    /// No cover of a component was found up to the size of its [`heuristic_cover`].
+    NoCover,
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleError::TooManyCovers => write!(f, "too many minimum covers to sample from"),
+            SampleError::NoCover => write!(f, "no minimum cover was found"),
+        }
+    }
+}
+
+impl Error for SampleError {}
+
+/// Draws `n` independent samples from the minimum biclique covers of `g`
+/// consisting of distinct maximal bicliques, each of them equally likely.
+///
+/// These are the covers counted by [`count_covers`] for the [`boolean_rank`].
+/// They are yielded by [`BicliqueCovers::maximal_only`] together
+/// with [`BicliqueCovers::minimum_only`].
+pub fn sample_minimum_covers<R: Rng + ?Sized>(
+    g: &Bigraph,
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<BicliqueCover>, SampleError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut samples = vec![Vec::new(); n];
+    for (component, map) in g.components() {
+        let mut covers = MinimumCovers::new(&component)?;
+        let mut picks: Vec<(u128, usize)> = (0..n)
+            .map(|sample| (rng.gen_range(0..covers.len()), sample))
+            .collect();
+        picks.sort_unstable();
+
+        for (sample, cliques) in covers.select(&picks, n).into_iter().enumerate() {
+            samples[sample].extend(cliques.iter().map(|c| c.lift(&map)));
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|cliques| BicliqueCover::new(g, cliques.into_boxed_slice()))
+        .collect())
+}
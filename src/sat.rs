@@ -0,0 +1,392 @@
+//! Finding minimum biclique covers with a small CDCL SAT solver, see [`Solver::Sat`].
+//!
+//! The formula is the one exported by [`dimacs::write_cnf`]. The solver uses the
+//! usual techniques: two watched literals per clause, learning first UIP clauses,
+//! VSIDS variable activities, phase saving and Luby restarts.
+use crate::reduce::Kernel;
+use crate::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::mem;
+use std::ops::Not;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Lit(u32);
+
+impl Lit {
+    fn new(var: usize, positive: bool) -> Lit {
+        Lit((var as u32) << 1 | u32::from(!positive))
+    }
+
+    fn from_dimacs(literal: i64) -> Lit {
+        Lit::new(literal.unsigned_abs() as usize - 1, literal > 0)
+    }
+
+    fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    fn is_positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+fn value(values: &[Option<bool>], lit: Lit) -> Option<bool> {
+    values[lit.var()].map(|v| v == lit.is_positive())
+}
+
+/// A variable together with its activity when it was added to the queue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate(f64, usize);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(other.1.cmp(&self.1))
+    }
+}
+
+struct Cdcl {
+    /// The first two literals of each clause are watched.
+    clauses: Vec<Vec<Lit>>,
+    /// For each literal, the clauses watching it.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    /// The clause which implied the value of each variable, its first literal
+    /// is the implied one. Decisions and units don't have a reason.
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// The start of each decision level in `trail`.
+    trail_limits: Vec<usize>,
+    propagated: usize,
+    activity: Vec<f64>,
+    increment: f64,
+    /// Unassigned variables ordered by their activity, possibly
+    /// containing outdated entries and assigned variables.
+    queue: BinaryHeap<Candidate>,
+    phases: Vec<bool>,
+    seen: Vec<bool>,
+    unsatisfiable: bool,
+}
+
+impl Cdcl {
+    fn new(variables: usize) -> Cdcl {
+        Cdcl {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * variables],
+            values: vec![None; variables],
+            levels: vec![0; variables],
+            reasons: vec![None; variables],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagated: 0,
+            activity: vec![0.0; variables],
+            increment: 1.0,
+            queue: (0..variables).map(|v| Candidate(0.0, v)).collect(),
+            phases: vec![false; variables],
+            seen: vec![false; variables],
+            unsatisfiable: false,
+        }
+    }
+
+    fn level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let v = lit.var();
+        self.values[v] = Some(lit.is_positive());
+        self.levels[v] = self.level();
+        self.reasons[v] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Adds a clause before solving.
+    fn add_clause(&mut self, mut clause: Vec<Lit>) {
+        debug_assert_eq!(self.level(), 0);
+        clause.sort_by_key(|l| l.0);
+        clause.dedup();
+        if clause.windows(2).any(|w| w[0] == !w[1])
+            || clause.iter().any(|&l| value(&self.values, l) == Some(true))
+        {
+            return;
+        }
+
+        clause.retain(|&l| value(&self.values, l).is_none());
+        match clause.len() {
+            0 => self.unsatisfiable = true,
+            1 => self.assign(clause[0], None),
+            _ => {
+                self.attach(clause);
+            }
+        }
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+        index
+    }
+
+    /// Propagates all assignments on the trail, returning a conflicting clause if there is one.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let falsified = !self.trail[self.propagated];
+            self.propagated += 1;
+
+            let mut watchers = mem::take(&mut self.watches[falsified.index()]);
+            let mut conflict = None;
+            let mut i = 0;
+            while i < watchers.len() {
+                let index = watchers[i];
+                let clause = &mut self.clauses[index];
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+                if value(&self.values, first) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+
+                let replacement =
+                    (2..clause.len()).find(|&j| value(&self.values, clause[j]) != Some(false));
+                if let Some(j) = replacement {
+                    clause.swap(1, j);
+                    self.watches[clause[1].index()].push(index);
+                    watchers.swap_remove(i);
+                } else if value(&self.values, first) == Some(false) {
+                    conflict = Some(index);
+                    break;
+                } else {
+                    self.assign(first, Some(index));
+                    i += 1;
+                }
+            }
+
+            self.watches[falsified.index()] = watchers;
+            if conflict.is_some() {
+                self.propagated = self.trail.len();
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+        if self.activity[v] > 1e100 {
+            for a in &mut self.activity {
+                *a *= 1e-100;
+            }
+            self.increment *= 1e-100;
+            self.queue = self
+                .activity
+                .iter()
+                .enumerate()
+                .map(|(v, &a)| Candidate(a, v))
+                .collect();
+        }
+
+        self.queue.push(Candidate(self.activity[v], v));
+    }
+
+    /// Derives the first UIP clause from a conflict, returning it together
+    /// with the level to backtrack to. Its first literal is the asserting one.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![Lit(0)];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut reason = conflict;
+        let mut skip = 0;
+        loop {
+            for j in skip..self.clauses[reason].len() {
+                let lit = self.clauses[reason][j];
+                let v = lit.var();
+                if !self.seen[v] && self.levels[v] > 0 {
+                    self.seen[v] = true;
+                    self.bump(v);
+                    if self.levels[v] == self.level() {
+                        pending += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            // Continue with the most recently assigned literal of the current level.
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+
+            let lit = self.trail[index];
+            self.seen[lit.var()] = false;
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = !lit;
+                break;
+            }
+
+            reason = self.reasons[lit.var()].unwrap();
+            skip = 1;
+        }
+
+        for lit in &learnt[1..] {
+            self.seen[lit.var()] = false;
+        }
+
+        // The literal with the highest level gets watched alongside the asserting one.
+        let mut level = 0;
+        if let Some(j) = (1..learnt.len()).max_by_key(|&j| self.levels[learnt[j].var()]) {
+            learnt.swap(1, j);
+            level = self.levels[learnt[1].var()];
+        }
+
+        (learnt, level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+
+        let start = self.trail_limits[level];
+        for lit in self.trail.drain(start..) {
+            let v = lit.var();
+            self.values[v] = None;
+            self.reasons[v] = None;
+            self.phases[v] = lit.is_positive();
+            self.queue.push(Candidate(self.activity[v], v));
+        }
+
+        self.trail_limits.truncate(level);
+        self.propagated = start;
+    }
+
+    fn decide(&mut self) -> Option<usize> {
+        while let Some(Candidate(_, v)) = self.queue.pop() {
+            if self.values[v].is_none() {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    /// Returns the value of each variable in a satisfying assignment, if there is one.
+    fn solve(mut self) -> Option<Vec<bool>> {
+        if self.unsatisfiable {
+            return None;
+        }
+
+        let mut restarts = 0;
+        let mut conflicts = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    return None;
+                }
+
+                conflicts += 1;
+                let (learnt, level) = self.analyze(conflict);
+                self.backtrack(level);
+                let asserting = learnt[0];
+                if learnt.len() == 1 {
+                    self.assign(asserting, None);
+                } else {
+                    let index = self.attach(learnt);
+                    self.assign(asserting, Some(index));
+                }
+                self.increment /= 0.95;
+            } else if conflicts >= 100 * luby(restarts) {
+                self.backtrack(0);
+                restarts += 1;
+                conflicts = 0;
+            } else if let Some(v) = self.decide() {
+                self.trail_limits.push(self.trail.len());
+                self.assign(Lit::new(v, self.phases[v]), None);
+            } else {
+                return Some(self.values.iter().map(|v| v.unwrap()).collect());
+            }
+        }
+    }
+}
+
+/// The `i`-th element of the Luby sequence `1, 1, 2, 1, 1, 2, 4, 1, ...`.
+fn luby(mut i: u64) -> u64 {
+    let mut size = 1;
+    let mut power = 1;
+    while size < i + 1 {
+        size = 2 * size + 1;
+        power *= 2;
+    }
+
+    while size - 1 != i {
+        size = (size - 1) / 2;
+        power /= 2;
+        i %= size;
+    }
+
+    power
+}
+
+/// Looks for a cover of `g` with at most `k` bicliques.
+fn solve(g: &Bigraph, k: usize, fooling_set: &forced::FoolingSet) -> Option<Vec<Biclique>> {
+    let formula = dimacs::encode(g, k, fooling_set);
+    let mut cdcl = Cdcl::new(formula.variables as usize);
+    for clause in formula.clauses {
+        cdcl.add_clause(clause.into_iter().map(Lit::from_dimacs).collect());
+    }
+
+    let model = cdcl.solve()?;
+    Some(dimacs::cliques(g, k, &model))
+}
+
+/// Like [`covers::minimum_cover`], but asks the SAT solver for smaller
+/// covers until the formula becomes unsatisfiable.
+pub(crate) fn minimum_cover(g: &Bigraph) -> Box<[Biclique]> {
+    let mut cliques = Vec::new();
+    for (component, map) in g.components() {
+        let kernel = Kernel::new(&component, true);
+        let fooling_set = forced::fooling_set(&kernel.graph);
+        let mut best = heuristic_cover(&kernel.graph, HeuristicOptions::default())
+            .cliques()
+            .to_vec();
+        while best.len() > fooling_set.len() {
+            match solve(&kernel.graph, best.len() - 1, &fooling_set) {
+                Some(found) => best = found,
+                None => break,
+            }
+        }
+
+        cliques.extend(kernel.lift_all(&best).iter().map(|c| c.lift(&map)));
+    }
+
+    cliques.into_boxed_slice()
+}
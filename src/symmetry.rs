@@ -0,0 +1,367 @@
+//! Automorphisms of bigraphs, i.e. permutations of the rows and columns
+//! which map every entry to an entry of the same kind.
+//!
+//! We find them by individualization and refinement: rows and columns are
+//! colored by the colors of their neighbors until the coloring is stable.
+//! Giving a single row or column a color of its own and refining again
+//! eventually leaves every row and column with a color of its own. Two such
+//! sequences of choices leading to the same colors give a candidate
+//! automorphism, which we only have to check.
+//!
+//! The rows and columns chosen along the first sequence form a base of the
+//! group. Going from the last one back to the first, we look for automorphisms
+//! which map it to each element of its cell, unless the automorphisms found
+//! so far already do. This yields a strong generating set.
+use crate::*;
+use std::collections::{HashSet, VecDeque};
+
+/// A permutation of the rows and the columns of a bigraph,
+/// see [`Bigraph::automorphisms`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Automorphism {
+    left: Box<[u32]>,
+    right: Box<[u32]>,
+}
+
+impl Automorphism {
+    /// Maps the row `x` to `left[x]` and the column `y` to `right[y]`.
+    ///
+    /// Use [`Bigraph::is_automorphism`] to check whether this is an automorphism.
+    pub fn new(left: Box<[u32]>, right: Box<[u32]>) -> Automorphism {
+        Automorphism { left, right }
+    }
+
+    pub fn identity(left: u32, right: u32) -> Automorphism {
+        Automorphism {
+            left: (0..left).collect(),
+            right: (0..right).collect(),
+        }
+    }
+
+    /// The row which the row `x` is mapped to.
+    pub fn left(&self, x: u32) -> u32 {
+        self.left[x as usize]
+    }
+
+    /// The column which the column `y` is mapped to.
+    pub fn right(&self, y: u32) -> u32 {
+        self.right[y as usize]
+    }
+
+    pub fn edge(&self, Edge(x, y): Edge) -> Edge {
+        Edge(self.left(x), self.right(y))
+    }
+
+    pub fn biclique(&self, clique: &Biclique) -> Biclique {
+        Biclique {
+            left: clique.left.iter().map(|x| self.left(x)).collect(),
+            right: clique.right.iter().map(|y| self.right(y)).collect(),
+        }
+    }
+
+    pub fn cover(&self, cover: &BicliqueCover) -> BicliqueCover {
+        let mut cover = BicliqueCover {
+            elements: cover.elements.iter().map(|c| self.biclique(c)).collect(),
+        };
+        cover.canonicalize();
+        cover
+    }
+
+    /// The permutation of the rows, which can be passed to [`Bigraph::permute`].
+    pub fn rows(&self) -> &[u32] {
+        &self.left
+    }
+
+    /// The permutation of the columns, which can be passed to [`Bigraph::permute`].
+    pub fn columns(&self) -> &[u32] {
+        &self.right
+    }
+
+    pub fn is_identity(&self) -> bool {
+        let fixed = |perm: &[u32]| perm.iter().enumerate().all(|(i, &j)| i as u32 == j);
+        fixed(&self.left) && fixed(&self.right)
+    }
+
+    /// The automorphism which applies `self` first and `other` afterwards.
+    pub fn then(&self, other: &Automorphism) -> Automorphism {
+        Automorphism {
+            left: self.left.iter().map(|&x| other.left(x)).collect(),
+            right: self.right.iter().map(|&y| other.right(y)).collect(),
+        }
+    }
+
+    pub fn inverse(&self) -> Automorphism {
+        let invert = |perm: &[u32]| {
+            let mut inverse = vec![0; perm.len()];
+            for (i, &j) in perm.iter().enumerate() {
+                inverse[j as usize] = i as u32;
+            }
+            inverse.into_boxed_slice()
+        };
+
+        Automorphism {
+            left: invert(&self.left),
+            right: invert(&self.right),
+        }
+    }
+
+    /// Splits a permutation of all rows and columns as used by [`Refiner`].
+    fn from_vertices(g: &Bigraph, perm: &[u32]) -> Automorphism {
+        let (left, right) = perm.split_at(g.left as usize);
+        Automorphism {
+            left: left.into(),
+            right: right.iter().map(|&y| y - g.left).collect(),
+        }
+    }
+
+    fn vertex(&self, g: &Bigraph, v: u32) -> u32 {
+        if v < g.left {
+            self.left(v)
+        } else {
+            self.right(v - g.left) + g.left
+        }
+    }
+}
+
+/// Refines colorings of the rows and columns of a bigraph, which are numbered
+/// `0..left` and `left..left + right` respectively.
+struct Refiner<'g> {
+    g: &'g Bigraph,
+    /// For each row or column, the other side of its entries
+    /// and whether the entry is optional.
+    neighbors: Vec<Vec<(u32, bool)>>,
+}
+
+impl<'g> Refiner<'g> {
+    fn new(g: &'g Bigraph) -> Refiner<'g> {
+        let mut neighbors = vec![Vec::new(); (g.left + g.right) as usize];
+        for x in 0..g.left {
+            for y in 0..g.right {
+                let optional = match g.cell(Edge(x, y)) {
+                    Cell::Required => false,
+                    Cell::Optional => true,
+                    Cell::Forbidden => continue,
+                };
+                neighbors[x as usize].push((g.left + y, optional));
+                neighbors[(g.left + y) as usize].push((x, optional));
+            }
+        }
+
+        Refiner { g, neighbors }
+    }
+
+    /// The stable coloring which distinguishes rows from columns and
+    /// rows or columns with different `labels`.
+    fn initial(&self, labels: &[u32]) -> Vec<u32> {
+        let kinds: Vec<(bool, u32)> = (0..self.g.left + self.g.right)
+            .map(|v| (v >= self.g.left, labels[v as usize]))
+            .collect();
+        let mut distinct = kinds.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        let mut colors: Vec<u32> = kinds
+            .iter()
+            .map(|kind| distinct.binary_search(kind).unwrap() as u32)
+            .collect();
+        self.refine(&mut colors);
+        colors
+    }
+
+    /// Splits the colors by the colors of the neighbors until that doesn't change
+    /// anything. The new colors only depend on the old ones and the bigraph, so
+    /// an automorphism mapping the old colors onto other colors also maps the
+    /// refined colors onto the refined other ones.
+    fn refine(&self, colors: &mut [u32]) {
+        let mut count = colors.iter().collect::<HashSet<_>>().len();
+        loop {
+            let signatures: Vec<(u32, Vec<(bool, u32)>)> = self
+                .neighbors
+                .iter()
+                .zip(colors.iter())
+                .map(|(neighbors, &color)| {
+                    let mut around: Vec<(bool, u32)> = neighbors
+                        .iter()
+                        .map(|&(u, optional)| (optional, colors[u as usize]))
+                        .collect();
+                    around.sort_unstable();
+                    (color, around)
+                })
+                .collect();
+
+            let mut distinct: Vec<&(u32, Vec<(bool, u32)>)> = signatures.iter().collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            for (color, signature) in colors.iter_mut().zip(&signatures) {
+                *color = distinct.binary_search(&signature).unwrap() as u32;
+            }
+
+            if distinct.len() == count {
+                return;
+            }
+            count = distinct.len();
+        }
+    }
+
+    /// Gives `v` a color of its own and refines the result.
+    fn individualize(&self, colors: &[u32], v: u32) -> Vec<u32> {
+        let mut colors = colors.to_vec();
+        colors[v as usize] = colors.len() as u32;
+        self.refine(&mut colors);
+        colors
+    }
+
+    /// The members of the first color shared by multiple rows or columns, if any.
+    fn target_cell(colors: &[u32]) -> Option<Vec<u32>> {
+        let mut sizes = vec![0; colors.len()];
+        for &color in colors {
+            sizes[color as usize] += 1;
+        }
+
+        let color = sizes.iter().position(|&size| size > 1)? as u32;
+        Some(
+            (0..colors.len() as u32)
+                .filter(|&v| colors[v as usize] == color)
+                .collect(),
+        )
+    }
+
+    /// Looks for an automorphism mapping the coloring `from` onto `to`.
+    fn find(&self, from: &[u32], to: &[u32]) -> Option<Automorphism> {
+        let histogram = |colors: &[u32]| {
+            let mut sizes = vec![0; colors.len()];
+            for &color in colors {
+                sizes[color as usize] += 1;
+            }
+            sizes
+        };
+        if histogram(from) != histogram(to) {
+            return None;
+        }
+
+        let Some(cell) = Refiner::target_cell(from) else {
+            let mut perm = vec![0; from.len()];
+            for (v, &color) in from.iter().enumerate() {
+                perm[v] = to.iter().position(|&c| c == color).unwrap() as u32;
+            }
+            let left = self.g.left as usize;
+            if perm[..left].iter().any(|&v| v as usize >= left) {
+                return None;
+            }
+
+            let automorphism = Automorphism::from_vertices(self.g, &perm);
+            return self
+                .g
+                .is_automorphism(&automorphism)
+                .then_some(automorphism);
+        };
+
+        let color = from[cell[0] as usize];
+        let from = self.individualize(from, cell[0]);
+        (0..to.len() as u32)
+            .filter(|&w| to[w as usize] == color)
+            .find_map(|w| self.find(&from, &self.individualize(to, w)))
+    }
+}
+
+/// The orbit of `v` under the group generated by `generators`.
+fn orbit(g: &Bigraph, generators: &[Automorphism], v: u32) -> HashSet<u32> {
+    let mut orbit = HashSet::from([v]);
+    let mut queue = VecDeque::from([v]);
+    while let Some(v) = queue.pop_front() {
+        for a in generators {
+            let w = a.vertex(g, v);
+            if orbit.insert(w) {
+                queue.push_back(w);
+            }
+        }
+    }
+    orbit
+}
+
+impl Bigraph {
+    /// A set of automorphisms generating all automorphisms of `self`, which is
+    /// empty if there are none besides the identity.
+    ///
+    /// An automorphism maps rows to rows and columns to columns, and every entry
+    /// to an entry of the same [`Cell`] kind. [`BicliqueCovers::with_symmetry`]
+    /// uses them to skip symmetric parts of the search.
+    pub fn automorphisms(&self) -> Vec<Automorphism> {
+        self.automorphisms_preserving(&vec![0; (self.left + self.right) as usize])
+    }
+
+    /// Like [`Bigraph::automorphisms`], but only for the automorphisms which
+    /// map each row and column to one with the same label. The `labels`
+    /// of the rows come first, followed by the ones of the columns.
+    pub(crate) fn automorphisms_preserving(&self, labels: &[u32]) -> Vec<Automorphism> {
+        let refiner = Refiner::new(self);
+        let mut colors = refiner.initial(labels);
+        let mut levels = Vec::new();
+        while let Some(cell) = Refiner::target_cell(&colors) {
+            let next = refiner.individualize(&colors, cell[0]);
+            levels.push((colors, cell));
+            colors = next;
+        }
+
+        // The automorphisms found for the later levels fix the base
+        // points of all earlier ones, so they're part of each stabilizer.
+        let mut generators: Vec<Automorphism> = Vec::new();
+        for (colors, cell) in levels.into_iter().rev() {
+            let base = refiner.individualize(&colors, cell[0]);
+            let mut reached = orbit(self, &generators, cell[0]);
+            for &w in &cell[1..] {
+                if reached.contains(&w) {
+                    continue;
+                }
+
+                if let Some(a) = refiner.find(&base, &refiner.individualize(&colors, w)) {
+                    generators.push(a);
+                    reached = orbit(self, &generators, cell[0]);
+                }
+            }
+        }
+
+        generators
+    }
+
+    /// Whether `a` permutes the rows and columns of `self`
+    /// and maps every entry to an entry of the same kind.
+    pub fn is_automorphism(&self, a: &Automorphism) -> bool {
+        let is_permutation = |perm: &[u32], len: u32| {
+            let seen: TBitSet<u32> = perm.iter().copied().filter(|&i| i < len).collect();
+            perm.len() == len as usize && seen.element_count() == perm.len()
+        };
+
+        is_permutation(&a.left, self.left)
+            && is_permutation(&a.right, self.right)
+            && (0..self.left).all(|x| {
+                (0..self.right).all(|y| self.cell(Edge(x, y)) == self.cell(a.edge(Edge(x, y))))
+            })
+    }
+}
+
+/// The elements of the group generated by `generators`, except for the
+/// identity, stopping once there are `limit` of them.
+pub(crate) fn group_elements(
+    g: &Bigraph,
+    generators: &[Automorphism],
+    limit: usize,
+) -> Vec<Automorphism> {
+    let identity = Automorphism::identity(g.left, g.right);
+    let mut seen = HashSet::from([identity.clone()]);
+    let mut elements = Vec::new();
+    let mut queue = VecDeque::from([identity]);
+    while let Some(a) = queue.pop_front() {
+        for generator in generators {
+            if elements.len() >= limit {
+                return elements;
+            }
+
+            let b = a.then(generator);
+            if seen.insert(b.clone()) {
+                elements.push(b.clone());
+                queue.push_back(b);
+            }
+        }
+    }
+    elements
+}
@@ -5,13 +5,10 @@ use std::collections::HashSet;
 
 fn all_solutions(g: &Bigraph, k: usize) -> HashSet<BicliqueCover> {
     let mut cliques = HashSet::new();
-    enum Never {}
-    biclique_covers::<Never, _>(&g, k, |c| {
-        if g.is_maximal_cover(&c) {
-            assert!(cliques.insert(c));
-        }
-        ControlFlow::Continue(())
-    });
+    for c in BicliqueCovers::new(g, k).maximal_only() {
+        assert!(g.is_maximal_cover(&c));
+        assert!(cliques.insert(c));
+    }
     cliques
 }
 
@@ -109,7 +106,21 @@ fn syn_le_min() {
         ],
         &g,
         &solutions,
-    )
+    );
+
+    // The iterator yields the same maximal covers among the others.
+    let covers: Vec<_> = BicliqueCovers::new(&g, 4).collect();
+    assert!(covers.iter().all(|c| is_cover(&g, c)));
+    let maximal: HashSet<_> = covers
+        .iter()
+        .filter(|c| g.is_maximal_cover(c))
+        .cloned()
+        .collect();
+    assert_eq!(maximal, solutions);
+
+    let mut iter = BicliqueCovers::new(&g, 4);
+    assert_eq!(iter.next().as_ref(), covers.first());
+    assert_eq!(iter.nth(2).as_ref(), covers.get(3));
 }
 
 #[test]
@@ -193,7 +204,6 @@ fn containment_err() {
             "000011|001111 100001|011110 111010|101000 100010|101110 010100|110101",
             "010101|010101 100001|011110 010110|100101 111010|101000 100010|101110",
             "000011|001111 100001|011110 111010|101000 110010|101100 010100|110101",
-            "000011|001111 100001|011110 111010|101000 111010|101000 010100|110101",
             "000011|001111 100001|011110 110110|100100 111010|101000 010100|110101",
             "010101|010101 100001|011110 110110|100100 111010|101000 000010|101111",
             "000011|001111 010101|010101 100001|011110 110110|100100 111010|101000",
@@ -231,3 +241,1076 @@ fn containment_err() {
         &solutions,
     )
 }
+
+#[test]
+fn minimum_cover() {
+    let g = Bigraph::from([[T, T], [F, T], [T, F]]);
+    assert_eq!(rank_with_engines(&g), 2);
+
+    let g = Bigraph::from([
+        [T, T, T, T, F],
+        [T, T, F, T, T],
+        [T, F, T, T, F],
+        [T, T, T, T, T],
+        [F, T, F, T, T],
+    ]);
+    let cover = minimum_cover_with_engines(&g);
+    assert_eq!(cover.cliques().len(), 3);
+    assert!(cover.cliques().iter().all(|c| !c.is_empty()));
+
+    assert_eq!(rank_with_engines(&Bigraph::new(3, 4)), 0);
+}
+
+#[test]
+fn fooling_set_bound() {
+    let g = Bigraph::from([
+        [T, T, T, T, T, F],
+        [T, T, T, T, F, T],
+        [T, F, T, F, F, F],
+        [T, T, F, T, F, T],
+        [T, F, T, T, T, T],
+        [F, T, T, T, T, T],
+    ]);
+    let fooling = forced::fooling_set(&g);
+    assert!(fooling.is_complete());
+    assert!(fooling.verify(&g));
+    assert!(fooling.len() <= rank_with_engines(&g));
+
+    let limited = forced::fooling_set_with_budget(&g, forced::Budget::Nodes(1));
+    assert!(limited.verify(&g));
+    assert!(limited.len() <= fooling.len());
+
+    assert!(!fooling.verify(&Bigraph::new(6, 6)));
+}
+
+#[test]
+fn partitions() {
+    let g = Bigraph::from([[T, T], [F, T], [T, F]]);
+    let mut partitions = HashSet::new();
+    enum Never {}
+    biclique_partitions::<Never, _>(&g, 4, |c| {
+        assert!(partitions.insert(c));
+        ControlFlow::Continue(())
+    });
+    check_solutions(
+        [
+            "101|10 110|01",
+            "101|10 010|01 100|01",
+            "001|10 110|01 100|10",
+            "001|10 010|01 100|11",
+            "001|10 010|01 100|01 100|10",
+        ],
+        &g,
+        &partitions,
+    );
+}
+
+#[test]
+fn optional_entries() {
+    const R: Cell = Cell::Required;
+    const O: Cell = Cell::Optional;
+    const N: Cell = Cell::Forbidden;
+
+    let g = Bigraph::from([[R, O], [O, R]]);
+    assert_eq!(rank_with_engines(&g), 1);
+
+    // A cover with optional entries is an exact cover of
+    // the bigraph where some optional entries are required.
+    let cells = [[R, R, N, O], [O, R, R, N], [R, N, R, R], [N, O, R, R]];
+    let g = Bigraph::from(cells);
+    let optional: Vec<Edge> = (0..4)
+        .flat_map(|x| (0..4).map(move |y| Edge(x, y)))
+        .filter(|&e| g.cell(e) == O)
+        .collect();
+    let mut expected = usize::MAX;
+    for mask in 0..1 << optional.len() {
+        let mut completed = Bigraph::from(cells);
+        for (i, &e) in optional.iter().enumerate() {
+            let cell = if mask & (1 << i) != 0 { R } else { N };
+            completed.set_cell(e, cell);
+        }
+        expected = expected.min(rank_with_engines(&completed));
+    }
+    assert_eq!(rank_with_engines(&g), expected);
+}
+
+#[test]
+fn maximal_biclique_enumeration() {
+    let g = Bigraph::from([
+        [T, T, T, T, T, F],
+        [T, T, T, T, F, T],
+        [T, F, T, F, F, F],
+        [T, T, F, T, F, T],
+        [T, F, T, T, T, T],
+        [F, T, T, T, T, T],
+    ]);
+    let bicliques: Vec<Biclique> = maximal_bicliques(&g).collect();
+    let unique: HashSet<Biclique> = bicliques.iter().cloned().collect();
+    assert_eq!(bicliques.len(), unique.len());
+    assert!(bicliques.iter().all(|c| g.is_maximal(c)));
+
+    // Every maximal biclique is the closure of its rows.
+    let mut expected = HashSet::new();
+    for rows in 1..1u32 << g.left() {
+        let left: TBitSet<u32> = (0..g.left()).filter(|x| rows & (1 << x) != 0).collect();
+        let right: TBitSet<u32> = (0..g.right())
+            .filter(|&y| left.iter().all(|x| g.get(Edge(x, y))))
+            .collect();
+        let clique = Biclique { left, right };
+        if !clique.right.is_empty() && g.is_maximal(&clique) {
+            expected.insert(clique);
+        }
+    }
+    assert_eq!(unique, expected);
+
+    for cover in all_solutions(&g, 5) {
+        assert!(cover.cliques().iter().all(|c| unique.contains(c)));
+    }
+}
+
+#[test]
+fn text_format() {
+    let s = "3 4\n1 1 _ ?\n_ 1 1 1\n? _ _ 1\n";
+    let g: Bigraph = s.parse().unwrap();
+    assert_eq!(g.cell(Edge(0, 3)), Cell::Optional);
+    assert_eq!(g.cell(Edge(1, 2)), Cell::Required);
+    assert_eq!(g.to_string(), s);
+    assert_eq!(g.to_string().parse::<Bigraph>(), Ok(g));
+
+    let err = |s: &str| s.parse::<Bigraph>().unwrap_err();
+    assert_eq!(
+        err("2 2\n1 1\n1  x\n"),
+        ParseBigraphError {
+            line: 3,
+            column: 4,
+            kind: ParseErrorKind::InvalidEntry("x".into()),
+        }
+    );
+    assert_eq!(err("2 -1").kind, ParseErrorKind::InvalidSize("-1".into()));
+    assert_eq!(
+        err("1 3000000000\n").kind,
+        ParseErrorKind::InvalidSize("3000000000".into())
+    );
+    assert_eq!((err("1 2\n1").line, err("1 2\n1").column), (2, 2));
+    assert_eq!(
+        err("1 1\n1 1").kind,
+        ParseErrorKind::TrailingInput("1".into())
+    );
+}
+
+#[test]
+fn checked_edges() {
+    let mut g = Bigraph::new(2, 3);
+    let err = OutOfRange {
+        edge: Edge(0, 3),
+        left: 2,
+        right: 3,
+    };
+    assert_eq!(g.try_add(Edge(0, 3)), Err(err));
+    assert_eq!(g.try_get(Edge(2, 0)).unwrap_err().edge, Edge(2, 0));
+    assert_eq!(g.try_add(Edge(1, 2)), Ok(()));
+    assert_eq!(g.try_get(Edge(1, 2)), Ok(true));
+    // `Edge(0, 3)` would otherwise alias `Edge(1, 0)`.
+    assert_eq!(g.try_get(Edge(1, 0)), Ok(false));
+
+    assert_eq!(
+        Bigraph::from_edges(2, 2, [Edge(0, 0), Edge(2, 1)]),
+        Err(OutOfRange {
+            edge: Edge(2, 1),
+            left: 2,
+            right: 2,
+        })
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of range")]
+fn add_out_of_range() {
+    Bigraph::new(2, 3).add(Edge(0, 3));
+}
+
+#[test]
+fn mutation() {
+    let mut g: Bigraph = [Edge(0, 0), Edge(1, 2), Edge(0, 1)].into_iter().collect();
+    assert_eq!((g.left(), g.right()), (2, 3));
+    assert_eq!(g, Bigraph::from([[T, T, F], [F, F, T]]));
+
+    g.remove(Edge(0, 1));
+    g.toggle(Edge(1, 0));
+    g.toggle(Edge(1, 2));
+    assert_eq!(g, Bigraph::from([[T, F, F], [T, F, F]]));
+
+    g.extend([Edge(0, 2), Edge(1, 1)]);
+    g.set_cell(Edge(0, 1), Cell::Optional);
+    g.clear_column(0);
+    assert_eq!(g.entries().collect::<Vec<_>>(), [Edge(0, 2), Edge(1, 1)]);
+
+    g.set_row(0, [0, 1]);
+    assert_eq!(g.cell(Edge(0, 1)), Cell::Required);
+    g.clear_row(1);
+    assert_eq!(g, Bigraph::from([[T, T, F], [F, F, F]]));
+
+    assert_eq!(Bigraph::from_iter([]), Bigraph::new(0, 0));
+}
+
+/// Whether `cover` covers exactly the edges of `g`, ignoring optional entries.
+fn is_cover(g: &Bigraph, cover: &BicliqueCover) -> bool {
+    (0..g.left()).all(|x| {
+        (0..g.right()).all(|y| {
+            let covered = cover.cliques().iter().any(|c| c.contains(Edge(x, y)));
+            match g.cell(Edge(x, y)) {
+                Cell::Required => covered,
+                Cell::Forbidden => !covered,
+                Cell::Optional => true,
+            }
+        })
+    })
+}
+
+/// The solvers which the minimum covers in these tests are computed with.
+fn engines() -> [Solver; 2] {
+    [Solver::Search, Solver::Sat]
+}
+
+/// Computes a minimum biclique cover with each of the [`engines`], checking that they agree.
+fn minimum_cover_with_engines(g: &Bigraph) -> BicliqueCover {
+    let [cover, other] = engines().map(|solver| minimum_biclique_cover_with_solver(g, solver));
+    assert!(is_cover(g, &cover) && is_cover(g, &other));
+    assert_eq!(cover.cliques().len(), other.cliques().len());
+    cover
+}
+
+/// The boolean rank of `g` computed with each of the [`engines`].
+fn rank_with_engines(g: &Bigraph) -> usize {
+    minimum_cover_with_engines(g).cliques().len()
+}
+
+#[test]
+fn transforms() {
+    let g = Bigraph::from([
+        [T, T, T, T, F],
+        [T, T, F, T, T],
+        [T, F, T, T, F],
+        [T, T, T, T, T],
+        [F, T, F, T, T],
+    ]);
+    let cover = minimum_cover_with_engines(&g);
+
+    let transposed = g.transpose();
+    assert_eq!(transposed.transpose(), g);
+    assert_eq!(transposed.cell(Edge(4, 0)), Cell::Forbidden);
+    assert!(is_cover(&transposed, &cover.transpose()));
+    assert_eq!(rank_with_engines(&transposed), 3);
+
+    let complement = g.complement();
+    assert_eq!(complement.entries().count(), 25 - g.entries().count());
+    assert_eq!(complement.complement(), g);
+
+    let (sub, map) = g.induced(&[4, 1, 2], &[1, 2, 3]);
+    assert_eq!(sub, Bigraph::from([[T, F, T], [T, F, T], [F, T, T]]));
+    let lifted = minimum_cover_with_engines(&sub).lift(&map);
+    for e in sub.entries() {
+        assert!(lifted.cliques().iter().any(|c| c.contains(map.edge(e))));
+    }
+
+    let (permuted, map) = g.permute(&[2, 0, 4, 1, 3], &[1, 2, 3, 4, 0]);
+    assert_eq!(permuted.cell(Edge(2, 3)), g.cell(Edge(0, 2)));
+    assert!(is_cover(
+        &g,
+        &minimum_cover_with_engines(&permuted).lift(&map)
+    ));
+}
+
+#[test]
+fn twins() {
+    // Rows 0 and 2 as well as columns 1 and 3 are twins,
+    // row 3 and column 4 are empty.
+    let g = Bigraph::from([
+        [T, T, F, T, F],
+        [F, T, T, T, F],
+        [T, T, F, T, F],
+        [F, F, F, F, F],
+        [T, F, T, F, F],
+    ]);
+    let (reduced, _) = g.induced(&[0, 1, 4], &[0, 1, 2]);
+
+    let maximal_covers = |g: &Bigraph| {
+        let mut covers = Vec::new();
+        for cover in BicliqueCovers::new(g, 4) {
+            assert!(is_cover(g, &cover));
+            if g.is_maximal_cover(&cover) {
+                covers.push(cover.cliques().len());
+            }
+        }
+        covers
+    };
+
+    assert_eq!(maximal_covers(&g), maximal_covers(&reduced));
+    assert_eq!(rank_with_engines(&g), rank_with_engines(&reduced));
+    assert_eq!(rank_with_engines(&Bigraph::new(3, 2)), 0);
+}
+
+#[test]
+fn components() {
+    let g = Bigraph::from([
+        [T, F, T, F, F],
+        [F, F, F, F, F],
+        [F, T, F, T, T],
+        [T, F, T, F, F],
+        [F, T, F, F, T],
+    ]);
+    let components = g.components();
+    assert_eq!(components.len(), 2);
+    let (first, map) = &components[0];
+    assert_eq!(*first, Bigraph::from([[T, T], [T, T]]));
+    assert_eq!(map.edge(Edge(1, 1)), Edge(3, 2));
+    let (second, map) = &components[1];
+    assert_eq!(*second, Bigraph::from([[T, T, T], [T, F, T]]));
+    assert_eq!(map.edge(Edge(1, 2)), Edge(4, 4));
+
+    let minimum = |g: &Bigraph| {
+        let rank = rank_with_engines(g);
+        BicliqueCovers::new(g, rank)
+            .filter(|c| c.cliques().len() == rank)
+            .count()
+    };
+    assert_eq!(rank_with_engines(&g), 3);
+    assert_eq!(minimum(&g), minimum(first) * minimum(second));
+
+    let sizes: Vec<_> = BicliqueCovers::new(&g, 5)
+        .inspect(|c| assert!(is_cover(&g, c)))
+        .map(|c| c.cliques().len())
+        .collect();
+    assert!(sizes.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(sizes[0], 3);
+
+    // Neither a single component nor a product pads smaller covers with empty bicliques.
+    for g in [&g, first, second] {
+        assert!(BicliqueCovers::new(g, 5).all(|c| c
+            .cliques()
+            .iter()
+            .all(|c| !c.left.is_empty() || !c.right.is_empty())));
+    }
+}
+
+#[test]
+fn generators() {
+    use rand::{rngs::StdRng, SeedableRng};
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let g = Bigraph::random(4, 6, 0.5, &mut rng);
+    assert_eq!((g.left(), g.right()), (4, 6));
+    assert_eq!(Bigraph::random(3, 3, 1.0, &mut rng).entries().count(), 9);
+
+    for k in 0..4 {
+        let (g, cover) = generate::planted_cover(5, 4, k, 0.4, &mut rng);
+        assert_eq!(cover.cliques().len(), k);
+        assert!(is_cover(&g, &cover));
+        assert!(rank_with_engines(&g) <= k);
+    }
+
+    let crown = generate::crown(6);
+    assert_eq!(
+        crown,
+        "6 6
+        1 1 1 1 1 _
+        1 1 1 1 _ 1
+        1 1 1 _ 1 1
+        1 1 _ 1 1 1
+        1 _ 1 1 1 1
+        _ 1 1 1 1 1"
+            .parse()
+            .unwrap()
+    );
+    assert_eq!(rank_with_engines(&crown), 4);
+
+    let g = generate::missing_per_row(5, 5, true, &mut rng);
+    assert_eq!(g.entries().count(), 20);
+    assert_eq!(rank_with_engines(&g), 4);
+    let g = generate::missing_per_row(6, 3, false, &mut rng);
+    assert!((0..6).all(|x| g.left_entries(x).count() == 2));
+}
+
+#[test]
+fn heuristic() {
+    use rand::{rngs::StdRng, SeedableRng};
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for rounds in [0, 4] {
+        let options = HeuristicOptions { rounds };
+        for _ in 0..20 {
+            let g = Bigraph::random(6, 7, 0.6, &mut rng);
+            let cover = heuristic_cover(&g, options);
+            assert!(is_cover(&g, &cover));
+            assert!(g.is_maximal_cover(&cover));
+            assert!(cover.cliques().len() >= rank_with_engines(&g));
+        }
+    }
+
+    let g = Bigraph::from([
+        [T, T, T, T, F],
+        [T, T, F, T, T],
+        [T, F, T, T, F],
+        [T, T, T, T, T],
+        [F, T, F, T, T],
+    ]);
+    let cover = heuristic_cover(&g, HeuristicOptions::default());
+    assert_eq!(cover.cliques().len(), 3);
+
+    let mut g = Bigraph::new(3, 3);
+    g.set_cell(Edge(1, 1), Cell::Optional);
+    let cover = heuristic_cover(&g, HeuristicOptions::default());
+    assert!(cover.cliques().is_empty());
+}
+
+#[test]
+fn limits() {
+    use std::time::Instant;
+
+    let g = generate::crown(6);
+    let rank = rank_with_engines(&g);
+    assert_eq!(rank, 4);
+    // The bounds have to enclose the rank, and the smallest cover
+    // which has been returned is an upper bound as well.
+    let check_bounds =
+        |completion: Completion, rank: usize, smallest: Option<usize>| match completion {
+            Completion::Complete => panic!("search should have been cut off"),
+            Completion::CutOff {
+                lower_bound,
+                upper_bound,
+            } => {
+                assert!(lower_bound <= rank);
+                assert!(upper_bound.is_none_or(|upper| upper >= rank));
+                if let Some(smallest) = smallest {
+                    assert!(upper_bound.is_some_and(|upper| upper <= smallest));
+                }
+            }
+        };
+
+    let limits = SearchLimits {
+        max_layers: Some(10),
+        ..SearchLimits::default()
+    };
+    let mut covers = BicliqueCovers::with_limits(&g, rank, limits);
+    let sizes: Vec<_> = covers.by_ref().map(|c| c.cliques().len()).collect();
+    assert!(sizes.len() < BicliqueCovers::new(&g, rank).count());
+    check_bounds(covers.completion(), rank, sizes.into_iter().min());
+
+    let limits = SearchLimits {
+        deadline: Some(Instant::now()),
+        ..SearchLimits::default()
+    };
+    let (cover, completion) = minimum_biclique_cover_with_limits(&g, limits);
+    assert!(is_cover(&g, &cover));
+    check_bounds(completion, rank, Some(cover.cliques().len()));
+
+    // Two copies of `g` next to each other.
+    let mut twice = Bigraph::new(12, 12);
+    twice.extend(
+        g.entries()
+            .flat_map(|Edge(x, y)| [Edge(x, y), Edge(x + 6, y + 6)]),
+    );
+    let limits = SearchLimits {
+        max_layers: Some(100),
+        ..SearchLimits::default()
+    };
+    let mut smallest = None;
+    let result = biclique_covers_with_limits::<(), _>(&twice, 2 * rank, limits, |c| {
+        let size = c.cliques().len();
+        smallest = Some(smallest.map_or(size, |s: usize| s.min(size)));
+        ControlFlow::Continue(())
+    });
+    match result {
+        ControlFlow::Continue(completion) => check_bounds(completion, 2 * rank, smallest),
+        ControlFlow::Break(()) => unreachable!(),
+    }
+
+    // Without containment, the search takes longer, but still runs until the end.
+    let limits = SearchLimits {
+        max_containment_entries: Some(0),
+        ..SearchLimits::default()
+    };
+    let (cover, completion) = minimum_biclique_cover_with_limits(&g, limits);
+    assert_eq!(completion, Completion::Complete);
+    assert_eq!(cover.cliques().len(), rank);
+    // The same covers may be found multiple times, but no maximal one goes missing.
+    let maximal = |covers: &mut BicliqueCovers| {
+        covers
+            .filter(|c| is_cover(&g, c) && g.is_maximal_cover(c))
+            .map(|c| c.print(&g))
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    let mut covers = BicliqueCovers::with_limits(&g, rank, limits);
+    assert_eq!(
+        maximal(&mut covers),
+        maximal(&mut BicliqueCovers::new(&g, rank))
+    );
+    assert_eq!(covers.completion(), Completion::Complete);
+}
+
+#[test]
+fn stats() {
+    use std::time::Duration;
+
+    let g = generate::crown(6);
+    let mut calls = 0;
+    let mut covers = BicliqueCovers::new(&g, 4).with_progress(Duration::ZERO, |stats| {
+        assert!(stats.layers > 0);
+        calls += 1;
+    });
+    assert!(covers.by_ref().count() > 0);
+
+    let stats = covers.stats().clone();
+    assert!(stats.layers > 0 && stats.branches > 0);
+    assert!(stats.discarded + stats.failed_updates > 0);
+    assert!(stats.containment_entries > 0);
+    // Sizes below the fooling set are never searched.
+    assert_eq!(stats.time_per_size.len(), 5);
+    assert_eq!(stats.time_per_size[0], Duration::ZERO);
+    drop(covers);
+    assert!(calls > 0);
+}
+
+#[test]
+fn parallel() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::{BTreeSet, HashSet};
+
+    let mut rng = StdRng::seed_from_u64(17);
+    let graphs = [
+        generate::crown(5),
+        Bigraph::random(6, 5, 0.6, &mut rng),
+        generate::planted_cover(7, 6, 3, 0.4, &mut rng).0,
+        Bigraph::from([
+            [T, T, F, F, F],
+            [T, F, T, F, F],
+            [F, T, T, F, F],
+            [F, F, F, T, T],
+        ]),
+    ];
+    for g in &graphs {
+        // The plain search finds the same covers as the sequential one.
+        let sequential: Vec<_> = BicliqueCovers::new(g, 4).collect();
+        let plain = |threads, deterministic| {
+            let options = ParallelOptions {
+                threads,
+                deterministic,
+            };
+            let mut covers = Vec::new();
+            let _ = par_biclique_covers::<(), _>(g, 4, options, |c| {
+                covers.push(c);
+                ControlFlow::Continue(())
+            });
+            covers
+        };
+        let covers = plain(3, true);
+        assert_eq!(covers, plain(1, true));
+        for covers in [covers, plain(3, false)] {
+            assert_eq!(covers.len(), sequential.len());
+            assert!(covers
+                .windows(2)
+                .all(|w| w[0].cliques().len() <= w[1].cliques().len()));
+            assert_eq!(
+                covers.into_iter().collect::<HashSet<_>>(),
+                sequential.iter().cloned().collect()
+            );
+        }
+
+        let collect = |threads: usize, deterministic: bool| {
+            let options = ParallelOptions {
+                threads,
+                deterministic,
+            };
+            let mut covers = Vec::new();
+            let _ = par_maximal_covers::<(), _>(g, 4, options, |c| {
+                assert!(is_cover(g, &c));
+                covers.push(c);
+                ControlFlow::Continue(())
+            });
+            covers
+        };
+
+        let maximal = |covers: &[BicliqueCover]| {
+            covers
+                .iter()
+                .filter(|c| g.is_maximal_cover(c))
+                .map(|c| c.print(g))
+                .collect::<BTreeSet<_>>()
+        };
+
+        let covers = collect(4, true);
+        assert!(covers.iter().all(|c| g.is_maximal_cover(c)));
+        assert_eq!(covers.iter().collect::<HashSet<_>>().len(), covers.len());
+        assert_eq!(maximal(&sequential), maximal(&covers));
+        assert!(covers
+            .windows(2)
+            .all(|w| w[0].cliques().len() <= w[1].cliques().len()));
+        assert_eq!(covers, collect(1, true));
+
+        let unordered = collect(3, false);
+        let distinct: HashSet<_> = unordered.iter().collect();
+        assert_eq!(distinct.len(), unordered.len());
+        assert_eq!(distinct, covers.iter().collect());
+    }
+}
+
+#[test]
+fn dimacs() {
+    use dimacs::DecodeError;
+
+    let mut g = Bigraph::from([[T, T, F, F], [T, T, T, F], [F, T, T, F], [F, F, T, T]]);
+    g.set_cell(Edge(2, 3), Cell::Optional);
+    let k = rank_with_engines(&g);
+    let fooling_set = forced::fooling_set(&g);
+    let mut cnf = Vec::new();
+    dimacs::write_cnf(&g, k, &fooling_set, &mut cnf).unwrap();
+    let cnf = String::from_utf8(cnf).unwrap();
+
+    // Put the bicliques of a minimum cover in the order required by the fooling set.
+    let mut cliques = minimum_cover_with_engines(&g).cliques().to_vec();
+    for (i, &e) in fooling_set.edges().iter().enumerate() {
+        let j = cliques.iter().position(|c| c.contains(e)).unwrap();
+        cliques.swap(i, j);
+    }
+
+    let width = (g.left() + g.right()) as usize;
+    let mut model = vec![false; k * width];
+    for (i, c) in cliques.iter().enumerate() {
+        for x in c.left() {
+            model[i * width + x as usize] = true;
+        }
+        for y in c.right() {
+            model[i * width + (g.left() + y) as usize] = true;
+        }
+    }
+    for e in g.entries() {
+        model.extend(cliques.iter().map(|c| c.contains(e)));
+    }
+
+    let mut lines = cnf.lines().filter(|l| !l.starts_with('c'));
+    let header: Vec<usize> = lines.next().unwrap()[6..]
+        .split(' ')
+        .map(|n| n.parse().unwrap())
+        .collect();
+    assert_eq!(header[0], model.len());
+    let clauses: Vec<&str> = lines.collect();
+    assert_eq!(header[1], clauses.len());
+    for clause in clauses {
+        let satisfied = clause.split(' ').any(|lit| {
+            let lit: i64 = lit.parse().unwrap();
+            lit != 0 && model[lit.unsigned_abs() as usize - 1] == (lit > 0)
+        });
+        assert!(satisfied, "clause `{}` is not satisfied", clause);
+    }
+
+    let literals: Vec<String> = model
+        .iter()
+        .enumerate()
+        .map(|(v, &b)| {
+            if b {
+                format!("{}", v + 1)
+            } else {
+                format!("-{}", v + 1)
+            }
+        })
+        .collect();
+    let solution = format!("s SATISFIABLE\nv {} 0\n", literals.join(" "));
+    let cover = dimacs::decode(&g, k, &solution).unwrap();
+    assert_eq!(cover, BicliqueCover::new(&g, cliques.into_boxed_slice()));
+
+    assert_eq!(
+        dimacs::decode(&g, k, "s UNSATISFIABLE\n"),
+        Err(DecodeError::NoSolution)
+    );
+    assert_eq!(
+        dimacs::decode(&g, k, "v -1 0\n"),
+        Err(DecodeError::NotACover)
+    );
+    assert_eq!(
+        dimacs::decode(&g, k, "v 1 x 0\n"),
+        Err(DecodeError::InvalidLiteral("x".into()))
+    );
+}
+
+#[test]
+fn sat_solver() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(19);
+    for _ in 0..40 {
+        let (left, right) = (rng.gen_range(1..8), rng.gen_range(1..8));
+        let mut g = Bigraph::random(left, right, rng.gen_range(0.3..0.9), &mut rng);
+        let e = Edge(rng.gen_range(0..left), rng.gen_range(0..right));
+        g.set_cell(e, Cell::Optional);
+
+        let cover = minimum_biclique_cover_with_solver(&g, Solver::Sat);
+        assert!(is_cover(&g, &cover));
+        assert_eq!(cover.cliques().len(), rank_with_engines(&g));
+    }
+
+    let g = generate::crown(7);
+    assert_eq!(boolean_rank_with_solver(&g, Solver::Sat), 5);
+}
+
+#[test]
+fn lp() {
+    use lp::{Objective, ParseSolutionError};
+    use std::collections::HashMap;
+
+    let mut g = Bigraph::from([
+        [T, T, F, F, T],
+        [T, T, T, F, F],
+        [F, T, T, F, T],
+        [F, F, T, T, T],
+    ]);
+    g.set_cell(Edge(2, 3), Cell::Optional);
+    let fooling_set = forced::fooling_set(&g);
+    let minimum = minimum_cover_with_engines(&g);
+
+    // Put the bicliques in the order required by the fooling set.
+    let mut cliques = minimum.cliques().to_vec();
+    for (i, &e) in fooling_set.edges().iter().enumerate() {
+        let j = cliques.iter().position(|c| c.contains(e)).unwrap();
+        cliques.swap(i, j);
+    }
+
+    let mut values = HashMap::new();
+    for (i, c) in cliques.iter().enumerate() {
+        values.insert(format!("u_{}", i), 1);
+        for x in c.left() {
+            values.insert(format!("r_{}_{}", i, x), 1);
+        }
+        for y in c.right() {
+            values.insert(format!("c_{}_{}", i, y), 1);
+        }
+        for Edge(x, y) in g.entries().filter(|&e| c.contains(e)) {
+            values.insert(format!("t_{}_{}_{}", i, x, y), 1);
+        }
+    }
+
+    for objective in [
+        Objective::AtMost(minimum.cliques().len()),
+        Objective::Minimum,
+    ] {
+        let mut program = Vec::new();
+        lp::write_lp(&g, objective, &fooling_set, &mut program).unwrap();
+        let program = String::from_utf8(program).unwrap();
+        assert!(!program.contains(" obj:\n"));
+
+        let constraints = program
+            .split("Subject To\n")
+            .nth(1)
+            .unwrap()
+            .split("Binary\n")
+            .next()
+            .unwrap()
+            .replace("\n   +", " +");
+        for constraint in constraints.lines() {
+            let tokens: Vec<&str> = constraint.split_whitespace().skip(1).collect();
+            let (&rhs, rest) = tokens.split_last().unwrap();
+            let (&op, terms) = rest.split_last().unwrap();
+            let mut sum = 0;
+            let mut sign = 1;
+            for &term in terms {
+                match term {
+                    "+" => sign = 1,
+                    "-" => sign = -1,
+                    name => sum += sign * values.get(name).copied().unwrap_or(0),
+                }
+            }
+
+            let rhs: i32 = rhs.parse().unwrap();
+            let satisfied = match op {
+                "<=" => sum <= rhs,
+                ">=" => sum >= rhs,
+                "=" => sum == rhs,
+                _ => panic!("unexpected operator `{}`", op),
+            };
+            assert!(satisfied, "constraint `{}` is not satisfied", constraint);
+        }
+    }
+
+    let mut solution = String::from("# Objective value = 3\n");
+    let mut xml = String::from("<?xml version = \"1.0\" standalone=\"yes\"?>\n<variables>\n");
+    for (name, value) in &values {
+        solution += &format!("{} {}\n", name, value);
+        xml += &format!(
+            "  <variable name=\"{}\" index=\"0\" value=\"{}\"/>\n",
+            name, value
+        );
+    }
+    xml += "</variables>\n";
+    assert_eq!(lp::parse_solution(&g, &solution), Ok(minimum.clone()));
+    assert_eq!(lp::parse_solution(&g, &xml), Ok(minimum));
+
+    assert_eq!(
+        lp::parse_solution(&g, "r_0_0 1\n"),
+        Err(ParseSolutionError::NotACover)
+    );
+    assert_eq!(
+        lp::parse_solution(&g, "r_0_9 1\n"),
+        Err(ParseSolutionError::OutOfRange("r_0_9".into()))
+    );
+    assert_eq!(
+        lp::parse_solution(&g, "c_0_0 one\n"),
+        Err(ParseSolutionError::InvalidValue("one".into()))
+    );
+}
+
+#[test]
+fn maximal_only() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    let mut rng = StdRng::seed_from_u64(21);
+    let graphs = [
+        generate::crown(5),
+        Bigraph::random(6, 5, 0.6, &mut rng),
+        generate::planted_cover(7, 6, 3, 0.4, &mut rng).0,
+        Bigraph::from([
+            [T, F, T, F, F],
+            [F, F, F, F, F],
+            [F, T, F, T, T],
+            [T, F, T, F, F],
+            [F, T, F, F, T],
+        ]),
+        "2 3\n? 1 _\n_ 1 1\n".parse().unwrap(),
+        "5 3\n? _ 1\n? _ _\n_ ? 1\n_ 1 1\n1 ? ?\n".parse().unwrap(),
+    ];
+    for g in &graphs {
+        // The same cover may be found with a biclique repeated.
+        let distinct = |c: &BicliqueCover| {
+            let mut cliques = c.cliques().to_vec();
+            biclique_sort(&mut cliques);
+            cliques.dedup();
+            BicliqueCover::new(g, cliques.into()).print(g)
+        };
+
+        let filtered: BTreeSet<_> = BicliqueCovers::new(g, 4)
+            .filter(|c| g.is_maximal_cover(c))
+            .map(|c| distinct(&c))
+            .collect();
+        let covers: Vec<_> = BicliqueCovers::new(g, 4).maximal_only().collect();
+        assert!(covers.iter().all(|c| g.is_maximal_cover(c)));
+        let printed: BTreeSet<_> = covers.iter().map(|c| c.print(g)).collect();
+        assert_eq!(printed.len(), covers.len());
+        // Without closing bicliques over optional entries,
+        // only some of the maximal covers are found.
+        if g.has_optional() {
+            assert!(filtered.is_subset(&printed));
+        } else {
+            assert_eq!(printed, filtered);
+        }
+
+        let mut counts = vec![0; 5];
+        for c in &covers {
+            counts[c.cliques().len()] += 1;
+        }
+        assert_eq!(counts, count_covers(g, 4));
+
+        let rank = rank_with_engines(g);
+        let minimum: Vec<_> = BicliqueCovers::new(g, 4)
+            .maximal_only()
+            .minimum_only()
+            .collect();
+        assert!(!minimum.is_empty());
+        assert!(minimum.iter().all(|c| c.cliques().len() == rank));
+        assert_eq!(
+            minimum.len(),
+            covers.iter().filter(|c| c.cliques().len() == rank).count()
+        );
+    }
+}
+
+#[test]
+fn counting() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(23);
+    let graphs = [
+        generate::crown(5),
+        Bigraph::random(6, 5, 0.6, &mut rng),
+        generate::planted_cover(7, 6, 3, 0.4, &mut rng).0,
+        Bigraph::from([
+            [T, F, T, F, F],
+            [F, F, F, F, F],
+            [F, T, F, T, T],
+            [T, F, T, F, F],
+            [F, T, F, F, T],
+        ]),
+    ];
+    for g in &graphs {
+        let mut expected = vec![0; 5];
+        for c in BicliqueCovers::new(g, 4).maximal_only() {
+            expected[c.cliques().len()] += 1;
+        }
+        assert_eq!(count_covers(g, 4), expected);
+    }
+
+    // Both blocks are needed, the row and the column in the middle are maximal
+    // bicliques as well, which may be added to them.
+    let g: Bigraph = "3 3\n1 1 _\n1 ? 1\n_ 1 1\n".parse().unwrap();
+    assert_eq!(count_covers(&g, 4), [0, 0, 1, 2, 1]);
+    assert_eq!(count_covers(&Bigraph::new(2, 2), 1), [1, 0]);
+}
+
+#[test]
+fn sampling() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashMap;
+
+    let mut rng = StdRng::seed_from_u64(22);
+    let g = Bigraph::from([
+        [F, T, T, T, F, F, F],
+        [T, F, T, T, F, F, F],
+        [T, T, F, T, F, F, F],
+        [T, T, T, F, F, F, F],
+        [F, F, F, F, T, T, F],
+        [F, F, F, F, F, T, T],
+        [F, F, F, F, T, F, T],
+    ]);
+    let rank = rank_with_engines(&g);
+    let mut counts: HashMap<_, usize> = BicliqueCovers::new(&g, rank)
+        .maximal_only()
+        .minimum_only()
+        .map(|c| (c.print(&g), 0))
+        .collect();
+
+    let n = 100 * counts.len();
+    let samples = sample_minimum_covers(&g, n, &mut rng).unwrap();
+    assert_eq!(samples.len(), n);
+    for c in &samples {
+        assert!(is_cover(&g, c));
+        *counts.get_mut(&c.print(&g)).unwrap() += 1;
+    }
+    assert!(counts.values().all(|&count| (50..150).contains(&count)));
+    assert_eq!(sample_minimum_covers(&g, 0, &mut rng), Ok(Vec::new()));
+
+    let g: Bigraph = "2 3\n? 1 _\n_ 1 1\n".parse().unwrap();
+    let samples: HashSet<_> = sample_minimum_covers(&g, 20, &mut rng)
+        .unwrap()
+        .into_iter()
+        .inspect(|c| assert!(is_cover(&g, c) && g.is_maximal_cover(c)))
+        .map(|c| c.print(&g))
+        .collect();
+    let covers: HashSet<_> = BicliqueCovers::new(&g, 2)
+        .maximal_only()
+        .minimum_only()
+        .map(|c| c.print(&g))
+        .collect();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples, covers);
+}
+
+#[test]
+fn incremental() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut cover = IncrementalCover::new(Bigraph::from([[T, T], [T, F]]));
+    assert_eq!(cover.cover().cliques().len(), 2);
+    cover.add(Edge(1, 1));
+    assert_eq!(cover.cover().cliques().len(), 1);
+    cover.remove(Edge(0, 0));
+    assert_eq!(cover.cover().cliques().len(), 2);
+
+    let mut rng = StdRng::seed_from_u64(24);
+    let mut cover = IncrementalCover::new(generate::planted_cover(7, 6, 3, 0.4, &mut rng).0);
+    for _ in 0..40 {
+        let e = Edge(rng.gen_range(0..7), rng.gen_range(0..6));
+        match rng.gen_range(0..5) {
+            0 | 1 => cover.add(e),
+            2 | 3 => cover.remove(e),
+            _ => cover.set_cell(e, Cell::Optional),
+        }
+
+        let g = cover.graph();
+        assert!(is_cover(g, cover.cover()));
+        assert!(cover.completion().is_complete());
+        assert_eq!(cover.cover().cliques().len(), rank_with_engines(g));
+    }
+}
+
+#[test]
+fn symmetry() {
+    use crate::symmetry::group_elements;
+
+    assert!(Bigraph::from([[T, F], [T, T]]).automorphisms().is_empty());
+
+    // Permuting the rows of the complement of a permutation matrix together
+    // with the matching columns preserves it.
+    let n = 5;
+    let mut g = Bigraph::new(n, n);
+    for x in 0..n {
+        for y in 0..n {
+            if x + y != n - 1 {
+                g.add(Edge(x, y));
+            }
+        }
+    }
+    let generators = g.automorphisms();
+    assert!(generators
+        .iter()
+        .all(|a| g.is_automorphism(a) && !a.is_identity()));
+    assert_eq!(group_elements(&g, &generators, usize::MAX).len() + 1, 120);
+
+    // Two components, one of them without required entries.
+    let mut optional = Bigraph::new(2, 2);
+    optional.add(Edge(0, 0));
+    optional.set_cell(Edge(1, 1), Cell::Optional);
+
+    // Two components that can't be swapped.
+    let mut crowns = Bigraph::new(7, 7);
+    for (offset, n) in [(0, 3), (3, 4)] {
+        for Edge(x, y) in generate::crown(n).entries() {
+            crowns.add(Edge(offset + x, offset + y));
+        }
+    }
+
+    for g in [
+        g,
+        generate::crown(4),
+        optional,
+        crowns,
+        Bigraph::new(0, 0),
+        Bigraph::new(2, 3),
+    ] {
+        let generators = g.automorphisms();
+        let k = rank_with_engines(&g);
+        let covers = |symmetry| -> Vec<BicliqueCover> {
+            BicliqueCovers::new(&g, k)
+                .maximal_only()
+                .with_symmetry(symmetry)
+                .collect()
+        };
+
+        assert_eq!(
+            BicliqueCovers::new(&g, k)
+                .with_symmetry(Symmetry::Orbits)
+                .count(),
+            BicliqueCovers::new(&g, k).count()
+        );
+
+        let all = all_solutions(&g, k);
+        let orbits = covers(Symmetry::Orbits);
+        assert_eq!(orbits.len(), all.len());
+        assert_eq!(orbits.into_iter().collect::<HashSet<_>>(), all);
+
+        // The orbits of the representatives partition all covers.
+        let mut reached = HashSet::new();
+        for cover in covers(Symmetry::Representatives) {
+            let mut orbit = vec![cover.clone()];
+            assert!(reached.insert(cover));
+            while let Some(cover) = orbit.pop() {
+                for a in &generators {
+                    let image = a.cover(&cover);
+                    if !reached.contains(&image) {
+                        orbit.push(image.clone());
+                        reached.insert(image);
+                    }
+                }
+            }
+        }
+        assert_eq!(reached, all);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::*;
+use std::{error::Error, str::FromStr};
+
+/// The textual representation of a bigraph.
+///
+/// It starts with the number of rows and columns, followed by one token for each
+/// entry: `1` for required edges, `_` for forbidden ones and `?` for optional ones.
+///
+/// ```plain
+/// 2 3
+/// 1 1 _
+/// _ ? 1
+/// ```
+impl fmt::Display for Bigraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.left(), self.right())?;
+        for x in 0..self.left() {
+            for y in 0..self.right() {
+                if y != 0 {
+                    write!(f, " ")?;
+                }
+
+                match self.cell(Edge(x, y)) {
+                    Cell::Required => write!(f, "1")?,
+                    Cell::Forbidden => write!(f, "_")?,
+                    Cell::Optional => write!(f, "?")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended before all entries have been read.
+    UnexpectedEnd,
+    /// The number of rows or columns is not a valid integer,
+    /// or the bigraph would be too large to index its entries.
+    InvalidSize(String),
+    /// An entry which is neither `1`, `_`, nor `?`.
+    InvalidEntry(String),
+    /// There is some input left after the last entry.
+    TrailingInput(String),
+}
+
+/// An error when parsing a [`Bigraph`], see its [`Display`](fmt::Display)
+/// implementation for the expected format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigraphError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseBigraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::InvalidSize(s) => write!(f, "invalid size `{}`", s),
+            ParseErrorKind::InvalidEntry(s) => {
+                write!(f, "invalid entry `{}`, expected `1`, `_` or `?`", s)
+            }
+            ParseErrorKind::TrailingInput(s) => {
+                write!(f, "unexpected `{}` after the last entry", s)
+            }
+        }
+    }
+}
+
+impl Error for ParseBigraphError {}
+
+struct Token<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl Token<'_> {
+    fn error(&self, kind: ParseErrorKind) -> ParseBigraphError {
+        ParseBigraphError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+fn tokens(s: &str) -> impl Iterator<Item = Token<'_>> {
+    s.lines().enumerate().flat_map(|(i, line)| {
+        line.split_whitespace().map(move |text| {
+            let offset = text.as_ptr() as usize - line.as_ptr() as usize;
+            Token {
+                line: i + 1,
+                column: line[..offset].chars().count() + 1,
+                text,
+            }
+        })
+    })
+}
+
+impl FromStr for Bigraph {
+    type Err = ParseBigraphError;
+
+    fn from_str(s: &str) -> Result<Bigraph, ParseBigraphError> {
+        let mut tokens = tokens(s);
+        let mut next = || {
+            tokens.next().ok_or_else(|| ParseBigraphError {
+                line: s.lines().count().max(1),
+                column: s.lines().last().map_or(0, |l| l.chars().count()) + 1,
+                kind: ParseErrorKind::UnexpectedEnd,
+            })
+        };
+        let mut size = || {
+            let token = next()?;
+            match token.text.parse::<u32>() {
+                Ok(size) => Ok((size, token)),
+                Err(_) => Err(token.error(ParseErrorKind::InvalidSize(token.text.into()))),
+            }
+        };
+
+        let (left, _) = size()?;
+        let (right, token) = size()?;
+        // The rows are stored at multiples of the column count rounded up to a power of two.
+        let fits = right
+            .checked_next_power_of_two()
+            .and_then(|stride| (left as usize).checked_mul(stride as usize))
+            .is_some();
+        if !fits {
+            return Err(token.error(ParseErrorKind::InvalidSize(token.text.into())));
+        }
+
+        let mut g = Bigraph::new(left, right);
+        for x in 0..left {
+            for y in 0..right {
+                let token = next()?;
+                let cell = match token.text {
+                    "1" => Cell::Required,
+                    "_" => Cell::Forbidden,
+                    "?" => Cell::Optional,
+                    text => return Err(token.error(ParseErrorKind::InvalidEntry(text.into()))),
+                };
+                g.set_cell(Edge(x, y), cell);
+            }
+        }
+
+        match tokens.next() {
+            Some(token) => Err(token.error(ParseErrorKind::TrailingInput(token.text.into()))),
+            None => Ok(g),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::*;
+
+/// Maps the rows and columns of a bigraph derived from some `g`,
+/// e.g. using [`Bigraph::induced`], back to the rows and columns of `g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexMap {
+    left: Box<[u32]>,
+    right: Box<[u32]>,
+}
+
+impl IndexMap {
+    pub fn new(left: Box<[u32]>, right: Box<[u32]>) -> IndexMap {
+        IndexMap { left, right }
+    }
+
+    pub fn left(&self, x: u32) -> u32 {
+        self.left[x as usize]
+    }
+
+    pub fn right(&self, y: u32) -> u32 {
+        self.right[y as usize]
+    }
+
+    pub fn edge(&self, Edge(x, y): Edge) -> Edge {
+        Edge(self.left(x), self.right(y))
+    }
+}
+
+fn assert_in_range(indices: &[u32], len: u32, kind: &str) {
+    if let Some(i) = indices.iter().find(|&&i| i >= len) {
+        panic!(
+            "{} {} is out of range for a bigraph with {} {}s",
+            kind, i, len, kind
+        );
+    }
+}
+
+fn assert_permutation(perm: &[u32], len: u32, kind: &str) {
+    assert_eq!(
+        perm.len(),
+        len as usize,
+        "{} permutation has the wrong length",
+        kind
+    );
+    assert_in_range(perm, len, kind);
+    let seen: TBitSet<u32> = perm.iter().copied().collect();
+    assert_eq!(
+        seen.element_count(),
+        perm.len(),
+        "invalid {} permutation",
+        kind
+    );
+}
+
+impl Bigraph {
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Bigraph {
+        let mut g = Bigraph::new(self.right, self.left);
+        for x in 0..self.left {
+            for y in 0..self.right {
+                g.set_cell(Edge(y, x), self.cell(Edge(x, y)));
+            }
+        }
+        g
+    }
+
+    /// Replaces all edges with non-edges and the other way around,
+    /// keeping optional entries as they are.
+    pub fn complement(&self) -> Bigraph {
+        let mut g = Bigraph::new(self.left, self.right);
+        for x in 0..self.left {
+            for y in 0..self.right {
+                let cell = match self.cell(Edge(x, y)) {
+                    Cell::Required => Cell::Forbidden,
+                    Cell::Forbidden => Cell::Required,
+                    Cell::Optional => Cell::Optional,
+                };
+                g.set_cell(Edge(x, y), cell);
+            }
+        }
+        g
+    }
+
+    /// Returns the bigraph only containing the given rows and columns, in that order,
+    /// together with a map from its rows and columns to the ones in `self`.
+    pub fn induced(&self, rows: &[u32], cols: &[u32]) -> (Bigraph, IndexMap) {
+        assert_in_range(rows, self.left, "row");
+        assert_in_range(cols, self.right, "column");
+        let mut g = Bigraph::new(rows.len() as u32, cols.len() as u32);
+        for (x, &row) in rows.iter().enumerate() {
+            for (y, &col) in cols.iter().enumerate() {
+                g.set_cell(Edge(x as u32, y as u32), self.cell(Edge(row, col)));
+            }
+        }
+
+        let map = IndexMap::new(rows.into(), cols.into());
+        (g, map)
+    }
+
+    /// Moves the row `x` to `row_perm[x]` and the column `y` to `col_perm[y]`.
+    ///
+    /// The returned map takes the rows and columns of the permuted
+    /// bigraph back to their original position.
+    pub fn permute(&self, row_perm: &[u32], col_perm: &[u32]) -> (Bigraph, IndexMap) {
+        assert_permutation(row_perm, self.left, "row");
+        assert_permutation(col_perm, self.right, "column");
+        let mut rows = vec![0; row_perm.len()];
+        for (x, &new) in row_perm.iter().enumerate() {
+            rows[new as usize] = x as u32;
+        }
+
+        let mut cols = vec![0; col_perm.len()];
+        for (y, &new) in col_perm.iter().enumerate() {
+            cols[new as usize] = y as u32;
+        }
+
+        self.induced(&rows, &cols)
+    }
+}
+
+impl Biclique {
+    pub fn transpose(&self) -> Biclique {
+        Biclique {
+            left: self.right.clone(),
+            right: self.left.clone(),
+        }
+    }
+
+    pub fn lift(&self, map: &IndexMap) -> Biclique {
+        Biclique {
+            left: self.left.iter().map(|x| map.left(x)).collect(),
+            right: self.right.iter().map(|y| map.right(y)).collect(),
+        }
+    }
+}
+
+impl BicliqueCover {
+    /// The corresponding cover of the transposed bigraph.
+    pub fn transpose(&self) -> BicliqueCover {
+        let mut cover = BicliqueCover {
+            elements: self.elements.iter().map(Biclique::transpose).collect(),
+        };
+        cover.canonicalize();
+        cover
+    }
+
+    /// Takes a cover of a bigraph derived from some `g` and
+    /// returns the corresponding bicliques in `g`.
+    pub fn lift(&self, map: &IndexMap) -> BicliqueCover {
+        let mut cover = BicliqueCover {
+            elements: self.elements.iter().map(|c| c.lift(map)).collect(),
+        };
+        cover.canonicalize();
+        cover
+    }
+}